@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use anyhow::{bail, Context, Result};
+
+use crate::answer::Answer;
+
+/// Known-correct answers, stored as TOML keyed by year, day and part:
+///
+/// ```toml
+/// [2023.1]
+/// part1 = "54667"
+/// part2 = "54203"
+/// ```
+///
+/// Answers are kept in their printed form so every `Answer` variant compares the same way.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    answers: BTreeMap<(u32, u32, u32), String>,
+}
+
+impl AnswerStore {
+    /// Reads the store at `path`, starting empty when the file does not exist yet.
+    pub fn load(path: &str) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("Invalid answers file {path}")),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Cannot read answers file {path}")),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let table: toml::Table = text.parse()?;
+        let mut answers = BTreeMap::new();
+        for (year, days) in &table {
+            let Some(days) = days.as_table() else {
+                bail!("`{year}` should be a table of days");
+            };
+            for (day, parts) in days {
+                let Some(parts) = parts.as_table() else {
+                    bail!("`{year}.{day}` should be a table of parts");
+                };
+                for (part, value) in parts {
+                    let answer = match value {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(n) => n.to_string(),
+                        _ => bail!("`{year}.{day}.{part}` should be a string or an integer"),
+                    };
+                    let key = (
+                        year.parse().with_context(|| format!("Invalid year `{year}`"))?,
+                        day.parse().with_context(|| format!("Invalid day `{day}`"))?,
+                        part.strip_prefix("part")
+                            .and_then(|p| p.parse().ok())
+                            .with_context(|| format!("Invalid part `{part}`, expected `part1` or `part2`"))?,
+                    );
+                    answers.insert(key, answer);
+                }
+            }
+        }
+        Ok(AnswerStore { answers })
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, part: u32, answer: &Answer) {
+        self.answers.insert((year, day, part), answer.to_string());
+    }
+
+    /// Writes the store back, ordered by year, day and part.
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, self.to_string()).with_context(|| format!("Cannot write answers file {path}"))
+    }
+}
+
+impl std::fmt::Display for AnswerStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut current = None;
+        for (&(year, day, part), answer) in &self.answers {
+            if current != Some((year, day)) {
+                if current.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[{year}.{day}]")?;
+                current = Some((year, day));
+            }
+            writeln!(f, "part{part} = {}", toml::Value::String(answer.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::answers::AnswerStore;
+
+    #[test]
+    fn test_round_trip() {
+        let mut store = AnswerStore::default();
+        store.insert(2024, 10, 1, &Answer::Int(36));
+        store.insert(2024, 2, 2, &Answer::Str("co,de,ka,ta".to_string()));
+        store.insert(2024, 2, 1, &Answer::Coord(6, 1));
+
+        let text = store.to_string();
+        assert_eq!(
+            text,
+            "[2024.2]\npart1 = \"6,1\"\npart2 = \"co,de,ka,ta\"\n\n[2024.10]\npart1 = \"36\"\n"
+        );
+        assert_eq!(AnswerStore::parse(&text).unwrap(), store);
+        assert_eq!(store.get(2024, 10, 1), Some("36"));
+        assert_eq!(store.get(2024, 10, 2), None);
+    }
+
+    #[test]
+    fn test_parse_integers_and_errors() {
+        let store = AnswerStore::parse("[2023.1]\npart1 = 142\n").unwrap();
+        assert_eq!(store.get(2023, 1, 1), Some("142"));
+        assert!(AnswerStore::parse("[2023.1]\nfirst = 142\n").is_err());
+        assert!(AnswerStore::parse("[2023]\n1 = 142\n").is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Solution;
+use crate::runner;
+
+/// How long to benchmark a day for.
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Untimed runs made before measuring.
+    pub warmup: usize,
+    /// Number of timed runs.
+    pub runs: usize,
+    /// Keep running until this much time has been spent measuring, on top of `runs`.
+    pub budget: Option<Duration>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 3,
+            runs: 10,
+            budget: None,
+        }
+    }
+}
+
+/// Summary of a set of timings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    #[serde(with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Cannot summarise an empty set of timings");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        // Nearest-rank percentile.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Timings of parsing and of both parts of a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bench {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl Bench {
+    pub fn steps(&self) -> [(&'static str, &Stats); 3] {
+        [("parse", &self.parse), ("part 1", &self.part_1), ("part 2", &self.part_2)]
+    }
+}
+
+/// Parses `input` and solves both parts from scratch on every run, so state left behind
+/// by a previous run never leaks into the next one.
+///
+/// Parse errors are reported against `name`.
+pub fn bench(solution: &Solution, name: &str, input: &str, options: &BenchOptions) -> Result<Bench> {
+    let run_once = || -> Result<[Duration; 3]> {
+        let start = Instant::now();
+        let day = (solution.build)(input).map_err(|err| err.with_file(name))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        day.part_1();
+        let part_1 = start.elapsed();
+
+        let start = Instant::now();
+        day.part_2();
+        let part_2 = start.elapsed();
+        Ok([parse, part_1, part_2])
+    };
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..options.warmup {
+            run_once()?;
+        }
+
+        let mut samples: [Vec<Duration>; 3] = Default::default();
+        let start = Instant::now();
+        while samples[0].len() < options.runs.max(1)
+            || options.budget.is_some_and(|budget| start.elapsed() < budget)
+        {
+            for (step, time) in samples.iter_mut().zip(run_once()?) {
+                step.push(time);
+            }
+        }
+
+        Ok(Bench {
+            parse: Stats::new(&samples[0]),
+            part_1: Stats::new(&samples[1]),
+            part_2: Stats::new(&samples[2]),
+        })
+    }))
+    .unwrap_or_else(|payload| Err(runner::panicked(payload)))
+}
+
+/// Saved benchmarks keyed by `"<year>/<day>"`, used to spot regressions between versions.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<String, Bench>,
+}
+
+impl Baseline {
+    fn key(year: u32, day: u32) -> String {
+        format!("{year}/{day}")
+    }
+
+    /// Reads the baseline at `path`, starting empty when the file does not exist yet.
+    pub fn load(path: &str) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).with_context(|| format!("Invalid baseline file {path}")),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Cannot read baseline file {path}")),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").with_context(|| format!("Cannot write baseline file {path}"))
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Bench> {
+        self.days.get(&Self::key(year, day))
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, bench: Bench) {
+        self.days.insert(Self::key(year, day), bench);
+    }
+}
+
+/// Change of the median of one step relative to the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub step: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+    /// Relative change, `0.1` meaning 10% slower.
+    pub change: f64,
+    pub regressed: bool,
+}
+
+/// Compares the medians of `current` against `baseline`, flagging every step that got
+/// slower by more than `threshold` (`0.1` for 10%).
+pub fn compare(baseline: &Bench, current: &Bench, threshold: f64) -> Vec<Comparison> {
+    baseline
+        .steps()
+        .into_iter()
+        .zip(current.steps())
+        .map(|((step, before), (_, after))| {
+            let (before, after) = (before.median, after.median);
+            let change = if before.is_zero() {
+                0.0
+            } else {
+                after.as_secs_f64() / before.as_secs_f64() - 1.0
+            };
+            Comparison {
+                step,
+                before,
+                after,
+                change,
+                regressed: change > threshold,
+            }
+        })
+        .collect()
+}
+
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_nanos(u64::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::bench::{self, Baseline, Bench, BenchOptions, Stats};
+    use crate::registry;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev.as_nanos(), 1581);
+
+        let stats = Stats::new(&micros(&[4, 2]));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(Stats::new(&micros(&[7])).stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_compare_and_baseline() {
+        let slow = Stats::new(&micros(&[200]));
+        let fast = Stats::new(&micros(&[100]));
+        let before = Bench { parse: fast, part_1: fast, part_2: slow };
+        let after = Bench { parse: fast, part_1: slow, part_2: fast };
+
+        let comparisons = bench::compare(&before, &after, 0.1);
+        let regressed: Vec<_> = comparisons.iter().filter(|c| c.regressed).map(|c| c.step).collect();
+        assert_eq!(regressed, vec!["part 1"]);
+        assert_eq!(comparisons[2].change, -0.5);
+
+        let mut baseline = Baseline::default();
+        baseline.insert(2023, 12, before.clone());
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap().get(2023, 12), Some(&before));
+    }
+
+    #[test]
+    fn test_bench() {
+        let solution = registry::find(2023, 1).unwrap();
+        let options = BenchOptions { warmup: 1, runs: 4, budget: None };
+        let result = bench::bench(solution, "sample", "1abc2\ntreb7uchet\n", &options).unwrap();
+        assert_eq!(result.part_2.runs, 4);
+        assert!(bench::bench(solution, "sample", "abc\n", &options).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc::geom::Point;
+use aoc::grid::Grid;
 use aoc::year2024::day10::Day10;
 use macroquad::prelude::*;
 
@@ -9,37 +11,25 @@ const BACKGROUND_COLOR: Color = WHITE;
 const TARGET_FPS: f32 = 30.0;
 
 fn graph_traverse(
-    graph: &Vec<Vec<u8>>,
-    x: usize,
-    y: usize,
+    graph: &Grid<u8>,
+    pos: Point,
     expected: u8,
-    visited: &mut HashSet<(usize, usize)>,
-    vis_arr: &mut Vec<(usize, usize)>,
+    visited: &mut HashSet<Point>,
+    vis_arr: &mut Vec<Point>,
 ) -> u64 {
-    if visited.contains(&(x, y)) {
+    if visited.contains(&pos) {
         return 0;
     }
-    visited.insert((x, y));
-    vis_arr.push((x, y));
-    if graph[y][x] == 9 {
+    visited.insert(pos);
+    vis_arr.push(pos);
+    if graph[pos] == 9 {
         return 1;
     }
     let mut score = 0;
-    // west
-    if x > 0 && graph[y][x - 1] == expected {
-        score += graph_traverse(graph, x - 1, y, expected + 1, visited, vis_arr);
-    }
-    // east
-    if x < graph[0].len() - 1 && graph[y][x + 1] == expected {
-        score += graph_traverse(graph, x + 1, y, expected + 1, visited, vis_arr);
-    }
-    // north
-    if y > 0 && graph[y - 1][x] == expected {
-        score += graph_traverse(graph, x, y - 1, expected + 1, visited, vis_arr);
-    }
-    // south
-    if y < graph.len() - 1 && graph[y + 1][x] == expected {
-        score += graph_traverse(graph, x, y + 1, expected + 1, visited, vis_arr);
+    for next in graph.neighbours4(pos) {
+        if graph[next] == expected {
+            score += graph_traverse(graph, next, expected + 1, visited, vis_arr);
+        }
     }
     score
 }
@@ -58,16 +48,16 @@ pub async fn main() {
     let colors = [
         DARKBLUE, DARKGREEN, DARKPURPLE, DARKBROWN, RED, BLACK
     ];
-    let mut data: Vec<Vec<(usize, usize, Color)>> = day
+    let mut data: Vec<Vec<(Point, Color)>> = day
         .starting
         .iter()
         .map(|start| {
             let mut visited = HashSet::new();
             let mut vis_arr = vec![];
-            graph_traverse(&day.lines, start.0, start.1, 1, &mut visited, &mut vis_arr);
+            graph_traverse(&day.lines, *start, 1, &mut visited, &mut vis_arr);
             vis_arr
                 .into_iter()
-                .map(|v| (v.0, v.1, Color::new(0.0, 0., 0., 0.)))
+                .map(|v| (v, Color::new(0.0, 0., 0., 0.)))
                 .collect()
         })
         .collect();
@@ -77,41 +67,39 @@ pub async fn main() {
     let frame_time = 1.0 / TARGET_FPS;
     let mut accumulator = 0.0;
 
-    data[i][ti].2 = colors[ci];
+    data[i][ti].1 = colors[ci];
     loop {
         let delta = get_frame_time();
         accumulator += delta;
         clear_background(BACKGROUND_COLOR);
-        for (y, line) in day.lines.iter().enumerate() {
-            for (x, digit) in line.iter().enumerate() {
-                let info = format!("{}", digit);
-                draw_text(
-                    &info,
-                    x as f32 * FONT_SIZE,
-                    y as f32 * FONT_SIZE,
-                    FONT_SIZE,
-                    FONT_COLOR,
-                );
-            }
+        for (pos, digit) in day.lines.iter() {
+            let info = format!("{}", digit);
+            draw_text(
+                &info,
+                pos.x as f32 * FONT_SIZE,
+                pos.y as f32 * FONT_SIZE,
+                FONT_SIZE,
+                FONT_COLOR,
+            );
         }
 
 
-        for j in 0..=i {
-            for point in &data[j] {
-                let info = format!("{}", day.lines[point.1][point.0]);
+        for step in &data[..=i] {
+            for (pos, color) in step {
+                let info = format!("{}", day.lines[*pos]);
                 draw_text(
                     &info,
-                    point.0 as f32 * FONT_SIZE,
-                    point.1 as f32 * FONT_SIZE,
+                    pos.x as f32 * FONT_SIZE,
+                    pos.y as f32 * FONT_SIZE,
                     FONT_SIZE,
-                    point.2,
+                    *color,
                 );
             }
         }
 
         if accumulator >= frame_time {
             accumulator -= frame_time;
-            data[i][ti].2 = colors[ci];
+            data[i][ti].1 = colors[ci];
             ti += 1;
             if ti == data[i].len() {
                 ti = 0;
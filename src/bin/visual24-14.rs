@@ -131,17 +131,16 @@ pub async fn main() {
         COLOR_GRID_LINES
     );
 
-    let mut day = Day14::new();
-    day.load();
+    let mut robots = Day14::new().robots();
     let mut second = 1;
     loop {
         clear_background(COLOR_BACKGROUND);
-        let guard_position: Vec<(i32, i32)> = day.data.iter().map(|&(pos, _)| pos).collect();
+        let guard_position: Vec<(i32, i32)> = robots.data.iter().map(|&(pos, _)| pos).collect();
         grid.draw_grid(&guard_position);
         let info = format!("Second {second}");
         draw_text(&info, 10.0, (GRID_HEIGHT as f32 * grid.cell_size) + 12., 20.0, COLOR_TEXT);
-        if !day.is_tree() {
-            day.step();
+        if !robots.is_tree() {
+            robots.step();
             second += 1;
         }
 
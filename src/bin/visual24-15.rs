@@ -24,37 +24,34 @@ fn window_conf() -> Conf {
 
 #[macroquad::main(window_conf)]
 pub async fn main() {
-    let mut day = Day15::new();
-    day.load();
-    day.modify_grid();
+    let day = Day15::new();
+    let mut warehouse = day.wide_warehouse();
     let movements = day.movement.clone();
     let total_movement = movements.len();
-    let mut start = Day15::get_start(&day.grid).expect("Start not found");
+    let mut start = warehouse.start().expect("Start not found");
     let mut i = 0;
     let mut accumulator = 0.0;
     let frame_time = 1.0 / TARGET_FPS;
-    let grid_size = day.grid.len();
-    let grid_w = day.grid[0].len();
+    let grid_size = warehouse.grid.height();
+    let grid_w = warehouse.grid.width();
     loop {
         clear_background(BACKGROUND_COLOR);
 
-        for (y, line) in day.grid.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                let color = match c {
-                    '@' => ROBOT_COLOR,
-                    '[' | ']' => BLOCK_COLOR,
-                    _ => FONT_COLOR,
-                };
+        for (pos, c) in warehouse.grid.iter() {
+            let color = match c {
+                '@' => ROBOT_COLOR,
+                '[' | ']' => BLOCK_COLOR,
+                _ => FONT_COLOR,
+            };
 
-                let info = format!("{}", c);
-                draw_text(
-                    &info,
-                    (x as f32 * (FONT_SIZE - X_OFF)) + X_PAD,
-                    (y as f32 * (FONT_SIZE - Y_OFF)) + Y_PAD,
-                    FONT_SIZE,
-                    color,
-                );
-            }
+            let info = format!("{}", c);
+            draw_text(
+                &info,
+                (pos.x as f32 * (FONT_SIZE - X_OFF)) + X_PAD,
+                (pos.y as f32 * (FONT_SIZE - Y_OFF)) + Y_PAD,
+                FONT_SIZE,
+                color,
+            );
         }
 
         let info = format!("Step {i}/{total_movement}");
@@ -80,13 +77,11 @@ pub async fn main() {
             BLACK,
         );
 
-        if i < movements.len() {
-            if accumulator >= frame_time {
-                accumulator -= frame_time;
-                let dir = &movements[i];
-                i += 1;
-                start = day.step(*dir, start);
-            }
+        if i < movements.len() && accumulator >= frame_time {
+            accumulator -= frame_time;
+            let dir = movements[i];
+            i += 1;
+            start = warehouse.step(dir, start);
         }
 
         next_frame().await;
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point, or the vector between two points, on an integer plane.
+///
+/// `x` grows to the right and `y` grows downwards, the way puzzle maps are read, so a
+/// `Point` doubles as the position of a cell in a `Grid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Offsets of all 8 neighbours, clockwise from up.
+pub const ALL_AROUND: [Point; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub const fn offset(self, dx: i32, dy: i32) -> Self {
+        Point::new(self.x + dx, self.y + dy)
+    }
+
+    /// The next point over in direction `dir`.
+    pub const fn step(self, dir: Direction) -> Self {
+        let offset = dir.offset();
+        self.offset(offset.x, offset.y)
+    }
+
+    /// The 4 orthogonal neighbours, in the order of `Direction::ALL`.
+    pub fn neighbours4(self) -> [Point; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// All 8 neighbours, clockwise from up.
+    pub fn neighbours8(self) -> [Point; 8] {
+        ALL_AROUND.map(|offset| self + offset)
+    }
+
+    pub fn manhattan(self, other: Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps count as one, like a king on a chessboard.
+    pub fn chebyshev(self, other: Point) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Square of the euclidean distance, which is exact and orders points the same way.
+    pub fn distance_squared(self, other: Point) -> i64 {
+        let (dx, dy) = ((self.x - other.x) as i64, (self.y - other.y) as i64);
+        dx * dx + dy * dy
+    }
+
+    pub fn euclidean(self, other: Point) -> f64 {
+        (self.distance_squared(other) as f64).sqrt()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A point in space. Coordinates are 64 bits wide since 3D puzzles tend to have large ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y)).max(self.z.abs_diff(other.z))
+    }
+
+    /// Square of the euclidean distance, which is exact and orders points the same way.
+    pub fn distance_squared(self, other: Point3) -> i64 {
+        let d = self - other;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+
+    pub fn euclidean(self, other: Point3) -> f64 {
+        (self.distance_squared(other) as f64).sqrt()
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+macro_rules! vector_ops {
+    ($point:ident, $scalar:ty, $($field:ident),+) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<$scalar> for $point {
+            type Output = $point;
+
+            fn mul(self, k: $scalar) -> $point {
+                $point { $($field: self.$field * k),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+vector_ops!(Point, i32, x, y);
+vector_ops!(Point3, i64, x, y, z);
+
+/// One of the four compass directions on a map, north being up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub const fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// The vector of one step in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    pub const fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Reads an arrow (`^>v<`), a compass point (`NESW`) or a move (`UDLR`).
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Direction::North),
+            '>' | 'E' | 'R' => Some(Direction::East),
+            'v' | 'S' | 'D' => Some(Direction::South),
+            '<' | 'W' | 'L' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The arrow pointing this way, as drawn on puzzle maps.
+    pub const fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::{Direction, Point, Point3};
+
+    #[test]
+    fn test_point() {
+        let a = Point::new(1, -2);
+        let b = Point::new(4, 2);
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(b - a, Point::new(3, 4));
+        assert_eq!(-a * 2, Point::new(-2, 4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(a.neighbours4()[1], Point::new(2, -2));
+        assert_eq!(a.neighbours8().len(), 8);
+
+        let p = Point3::new(1, 2, 3);
+        let q = Point3::new(-2, 6, 3);
+        assert_eq!(p - q, Point3::new(3, -4, 0));
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(p.distance_squared(q), 25);
+    }
+
+    #[test]
+    fn test_direction() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.offset() + dir.reverse().offset(), Point::ORIGIN);
+            assert_eq!(Direction::from_char(dir.arrow()), Some(dir));
+        }
+        assert_eq!(Point::ORIGIN.step(Direction::North), Point::new(0, -1));
+        assert_eq!(Direction::from_char('L'), Some(Direction::West));
+        assert_eq!(Direction::from_char('S'), Some(Direction::South));
+        assert_eq!(Direction::from_char('x'), None);
+        assert!(Direction::East.is_horizontal());
+    }
+}
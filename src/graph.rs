@@ -0,0 +1,370 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Gives each distinct name a small id, counting up from 0 in the order they are first seen.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// The id of `name`, giving it the next free one if it has none yet.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    /// Panics when no name has been given `id`.
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A graph on the nodes `0..len()`, stored as one sorted run of neighbours per node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+/// The cheapest way to split a graph in two, as found by `Graph::min_cut`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// The edges to remove, each from a node in `side` to one outside it.
+    pub edges: Vec<(usize, usize)>,
+    /// The nodes on one side of the cut, in increasing order.
+    pub side: Vec<usize>,
+    /// How many nodes are on the other side.
+    pub rest: usize,
+}
+
+impl Graph {
+    /// A graph with an edge from `a` to `b` for each `(a, b)` in `edges`. Repeated edges
+    /// are kept once.
+    pub fn directed(len: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        edges.sort_unstable();
+        edges.dedup();
+        let mut offsets = vec![0; len + 1];
+        for &(a, _) in &edges {
+            offsets[a + 1] += 1;
+        }
+        for i in 0..len {
+            offsets[i + 1] += offsets[i];
+        }
+        Graph { offsets, targets: edges.into_iter().map(|(_, b)| b).collect() }
+    }
+
+    /// A graph where each of `edges` goes both ways.
+    pub fn undirected(len: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        Graph::directed(len, edges.into_iter().flat_map(|(a, b)| [(a, b), (b, a)]))
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The nodes `node` has an edge to, in increasing order.
+    pub fn neighbours(&self, node: usize) -> &[usize] {
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    pub fn degree(&self, node: usize) -> usize {
+        self.neighbours(node).len()
+    }
+
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.neighbours(a).binary_search(&b).is_ok()
+    }
+
+    /// Every edge, in order of where it starts and then where it ends.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.len()).flat_map(move |a| self.neighbours(a).iter().map(move |&b| (a, b)))
+    }
+
+    /// The nodes reachable from one another along edges, each list in increasing order and
+    /// the lists ordered by their first node. Meant for undirected graphs.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for &next in self.neighbours(node) {
+                    if !seen[next] {
+                        seen[next] = true;
+                        component.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// The nodes ordered so every edge goes forwards, taking the lowest ready node first.
+    /// `None` when there is a cycle.
+    pub fn toposort(&self) -> Option<Vec<usize>> {
+        let mut indegree = vec![0; self.len()];
+        for (_, b) in self.edges() {
+            indegree[b] += 1;
+        }
+        let mut ready: VecDeque<usize> = (0..self.len()).filter(|&n| indegree[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &next in self.neighbours(node) {
+                indegree[next] -= 1;
+                if indegree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// How many distinct paths lead from `from` to `to`. Panics when one of them could run
+    /// round a cycle, since there would be no end to them.
+    pub fn count_paths(&self, from: usize, to: usize) -> u64 {
+        let mut memo = vec![None; self.len()];
+        self.paths_from(from, to, &mut memo, &mut vec![false; self.len()])
+    }
+
+    fn paths_from(&self, node: usize, to: usize, memo: &mut [Option<u64>], visiting: &mut [bool]) -> u64 {
+        if node == to {
+            return 1;
+        }
+        if let Some(paths) = memo[node] {
+            return paths;
+        }
+        assert!(!visiting[node], "Graph has a cycle through node {node}");
+        visiting[node] = true;
+        let paths = self.neighbours(node).iter().map(|&next| self.paths_from(next, to, memo, visiting)).sum();
+        visiting[node] = false;
+        memo[node] = Some(paths);
+        paths
+    }
+
+    /// Fewest edges from `from` to each node, `None` for those it cannot reach.
+    pub fn distances(&self, from: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[from] = Some(0);
+        let mut queue = VecDeque::from([(from, 0)]);
+        while let Some((node, distance)) = queue.pop_front() {
+            for &next in self.neighbours(node) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// `distances` from every node, indexed by the node they start at.
+    pub fn all_distances(&self) -> Vec<Vec<Option<usize>>> {
+        (0..self.len()).map(|node| self.distances(node)).collect()
+    }
+
+    /// Every triangle of an undirected graph, each in increasing order.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+        for a in 0..self.len() {
+            let above = |n: usize| self.neighbours(n).iter().copied().filter(move |&m| m > n);
+            for b in above(a) {
+                for c in above(b) {
+                    if self.has_edge(a, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// Every clique of an undirected graph that no other node could join, each in increasing
+    /// order, by Bron–Kerbosch with pivoting.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = Vec::new();
+        self.bron_kerbosch(&mut vec![], (0..self.len()).collect(), vec![], &mut cliques);
+        cliques
+    }
+
+    fn bron_kerbosch(&self, clique: &mut Vec<usize>, mut candidates: Vec<usize>, mut excluded: Vec<usize>, cliques: &mut Vec<Vec<usize>>) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut found = clique.clone();
+                found.sort_unstable();
+                cliques.push(found);
+            }
+            return;
+        }
+        // Any maximal clique holds the pivot or one of the nodes it is not joined to.
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .copied()
+            .max_by_key(|&p| candidates.iter().filter(|&&c| self.has_edge(p, c)).count())
+            .unwrap();
+        let branches: Vec<usize> = candidates.iter().copied().filter(|&c| !self.has_edge(pivot, c)).collect();
+        for node in branches {
+            let joined = |set: &[usize]| set.iter().copied().filter(|&n| self.has_edge(node, n)).collect();
+            clique.push(node);
+            self.bron_kerbosch(clique, joined(&candidates), joined(&excluded), cliques);
+            clique.pop();
+            candidates.retain(|&c| c != node);
+            excluded.push(node);
+        }
+    }
+
+    /// The fewest edges whose removal splits an undirected graph in two. Each node is tried
+    /// as the far side from node 0 with a unit-capacity max flow, so this suits the sparse
+    /// graphs puzzles use. `None` when there are fewer than two nodes.
+    pub fn min_cut(&self) -> Option<MinCut> {
+        let mut best: Option<(usize, Vec<bool>)> = None;
+        for sink in 1..self.len() {
+            let limit = best.as_ref().map_or(usize::MAX, |(cut, _)| *cut);
+            let (flow, side) = self.max_flow(0, sink, limit);
+            if flow < limit {
+                best = Some((flow, side));
+            }
+        }
+        let (_, side) = best?;
+        let edges = self.edges().filter(|&(a, b)| side[a] && !side[b]).collect();
+        let side: Vec<usize> = (0..self.len()).filter(|&n| side[n]).collect();
+        Some(MinCut { edges, rest: self.len() - side.len(), side })
+    }
+
+    /// Sends as many unit flows from `source` to `sink` as there is room for, stopping once
+    /// `limit` are through. Returns the flow and the nodes still reachable from `source`,
+    /// which are one side of a minimum cut when the flow is below `limit`.
+    fn max_flow(&self, source: usize, sink: usize, limit: usize) -> (usize, Vec<bool>) {
+        // Flow along each edge, as stored in `targets`.
+        let mut flow = vec![0_i8; self.targets.len()];
+        let edge = |a: usize, b: usize| self.offsets[a] + self.neighbours(a).binary_search(&b).unwrap();
+        let mut total = 0;
+        loop {
+            let mut parent = vec![usize::MAX; self.len()];
+            parent[source] = source;
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                if node == sink {
+                    break;
+                }
+                for (i, &next) in self.neighbours(node).iter().enumerate() {
+                    if parent[next] == usize::MAX && flow[self.offsets[node] + i] < 1 {
+                        parent[next] = node;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            if parent[sink] == usize::MAX || total == limit {
+                return (total, parent.iter().map(|&p| p != usize::MAX).collect());
+            }
+            let mut node = sink;
+            while node != source {
+                let prev = parent[node];
+                flow[edge(prev, node)] += 1;
+                flow[edge(node, prev)] -= 1;
+                node = prev;
+            }
+            total += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Interner};
+
+    /// Two squares with both diagonals, joined by the edges 3-4 and 2-5.
+    fn two_blobs() -> Graph {
+        let square = |o: usize| [(o, o + 1), (o + 1, o + 2), (o + 2, o + 3), (o + 3, o), (o, o + 2), (o + 1, o + 3)];
+        Graph::undirected(8, square(0).into_iter().chain(square(4)).chain([(3, 4), (2, 5)]))
+    }
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("kh"), 0);
+        assert_eq!(names.intern("tc"), 1);
+        assert_eq!(names.intern("kh"), 0);
+        assert_eq!((names.get("tc"), names.get("qp")), (Some(1), None));
+        assert_eq!(names.name(1), "tc");
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_structure() {
+        let graph = Graph::undirected(6, [(0, 1), (1, 2), (1, 0), (3, 4)]);
+        assert_eq!(graph.neighbours(1), &[0, 2]);
+        assert_eq!(graph.degree(5), 0);
+        assert!(graph.has_edge(4, 3) && !graph.has_edge(0, 2));
+        assert_eq!(graph.edges().count(), 6);
+        assert_eq!(graph.components(), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(graph.distances(0), vec![Some(0), Some(1), Some(2), None, None, None]);
+        assert_eq!(graph.all_distances()[4][3], Some(1));
+    }
+
+    #[test]
+    fn test_dag() {
+        // Two ways from 0 to 2, each followed by two ways on to 5.
+        let graph = Graph::directed(6, [(0, 1), (1, 2), (0, 2), (2, 3), (2, 4), (3, 5), (4, 5)]);
+        let order = graph.toposort().unwrap();
+        assert!(graph.edges().all(|(a, b)| order.iter().position(|&n| n == a) < order.iter().position(|&n| n == b)));
+        assert_eq!(graph.count_paths(0, 5), 4);
+        assert_eq!(graph.count_paths(3, 2), 0);
+        assert_eq!(Graph::directed(2, [(0, 1), (1, 0)]).toposort(), None);
+    }
+
+    #[test]
+    fn test_cliques() {
+        let graph = two_blobs();
+        assert_eq!(graph.triangles().len(), 8);
+        assert!(graph.triangles().contains(&[4, 5, 7]));
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2, 3], vec![2, 5], vec![3, 4], vec![4, 5, 6, 7]]);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let cut = two_blobs().min_cut().unwrap();
+        assert_eq!(cut.side, vec![0, 1, 2, 3]);
+        assert_eq!(cut.rest, 4);
+        assert_eq!(cut.edges, vec![(2, 5), (3, 4)]);
+        assert_eq!(Graph::undirected(1, []).min_cut(), None);
+    }
+}
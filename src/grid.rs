@@ -0,0 +1,278 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Direction, Point, ALL_AROUND};
+use crate::parse::{self, ParseError};
+
+/// A rectangular grid stored row by row in a single `Vec`, indexed by `Point`.
+///
+/// Points are signed so neighbours and offsets can step outside the grid and be checked
+/// afterwards with `contains` or `get`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Wraps `cells`, laid out row by row. Panics when they do not fill the grid exactly.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "A {width}x{height} grid needs {} cells", width * height);
+        Grid { width, height, cells }
+    }
+
+    /// Builds a grid from its rows. Panics when they do not all have the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let first = *width.get_or_insert(row.len());
+            assert_eq!(row.len(), first, "Row {height} has {} cells where the first row has {first}", row.len());
+            cells.extend(row);
+            height += 1;
+        }
+        Self::from_vec(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn offset(&self, pos: Point) -> Option<usize> {
+        self.contains(pos).then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Cell at `pos` on a grid repeating infinitely in every direction.
+    pub fn get_wrapping(&self, pos: Point) -> &T {
+        let x = pos.x.rem_euclid(self.width as i32);
+        let y = pos.y.rem_euclid(self.height as i32);
+        &self[Point::new(x, y)]
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The orthogonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL.into_iter().map(move |dir| pos.step(dir)).filter(|&p| self.contains(p))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        ALL_AROUND.into_iter().map(move |offset| pos + offset).filter(|&p| self.contains(p))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, and an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Builds a grid of the given size from the value of each position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+            .map(&mut f)
+            .collect();
+        Grid { width, height, cells }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Mirror image along the main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |p| self[Point::new(p.y, p.x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height as i32;
+        Grid::from_fn(self.height, self.width, |p| self[Point::new(p.y, height - 1 - p.x)].clone())
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.width as i32;
+        Grid::from_fn(self.height, self.width, |p| self[Point::new(width - 1 - p.y, p.x)].clone())
+    }
+
+    /// Mirror image left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width as i32;
+        Grid::from_fn(self.width, self.height, |p| self[Point::new(width - 1 - p.x, p.y)].clone())
+    }
+
+    /// Mirror image top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height as i32;
+        Grid::from_fn(self.width, self.height, |p| self[Point::new(p.x, height - 1 - p.y)].clone())
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.iter().find(|(_, cell)| *cell == value).map(|(pos, _)| pos)
+    }
+
+    /// Positions of every cell equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a {
+        self.iter().filter(move |(_, cell)| *cell == value).map(|(pos, _)| pos)
+    }
+}
+
+impl Grid<char> {
+    /// Parses `s`, a slice of `input`, as a rectangular grid of `allowed` characters.
+    pub fn parse(input: &str, s: &str, allowed: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, s, allowed, |c| c)
+    }
+
+    /// Like `parse`, converting every character with `f`.
+    pub fn parse_with<T>(input: &str, s: &str, allowed: &str, f: impl FnMut(char) -> T) -> Result<Grid<T>, ParseError> {
+        let rows = parse::grid(input, s, allowed)?;
+        let width = rows[0].chars().count();
+        let cells = rows.iter().flat_map(|row| row.chars()).map(f).collect();
+        Ok(Grid::from_vec(width, rows.len(), cells))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        match self.offset(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{pos:?} is outside of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        match self.offset(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{pos:?} is outside of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+/// One line per row, without separators between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::Point;
+    use crate::grid::Grid;
+
+    fn sample() -> Grid<char> {
+        let input = "ab#\n#cd\n";
+        Grid::parse(input, input, "abcd#").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_access() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'd');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(*grid.get_wrapping(Point::new(-1, 3)), 'd');
+        assert_eq!(grid.find(&'c'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), vec![Point::new(2, 0), Point::new(0, 1)]);
+        assert_eq!(grid.row(1), &['#', 'c', 'd']);
+        assert_eq!(grid.column(2).collect::<String>(), "#d");
+        assert_eq!(grid.to_string(), "ab#\n#cd");
+
+        let input = "ab\nabc\n";
+        let err = Grid::parse(input, input, "abc").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = sample();
+        let corner: Vec<Point> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "a#\nbc\n#d");
+        assert_eq!(grid.rotate_clockwise().to_string(), "#a\ncb\nd#");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "#d\nbc\na#");
+        assert_eq!(grid.flip_horizontal().to_string(), "#ba\ndc#");
+        assert_eq!(grid.flip_vertical().to_string(), "#cd\nab#");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = Grid::from_rows([vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(0, 1)], 4);
+        assert_eq!(Grid::<u8>::from_rows([]).width(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 1 has 4 cells where the first row has 2")]
+    fn test_from_ragged_rows() {
+        Grid::from_rows([vec![1, 2], vec![3, 4, 5, 6]]);
+    }
+}
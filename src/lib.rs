@@ -1,4 +1,31 @@
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod memory;
+pub mod parse;
+pub mod polygon;
+pub mod ranges;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod search;
 pub mod year2024;
 pub mod traits;
 pub mod year2023;
-pub mod year2025;
\ No newline at end of file
+pub mod year2025;
+
+use answer::Answer;
+
+/// Solves both parts of the given day against `input`.
+pub fn solve(year: u32, day: u32, input: &str) -> anyhow::Result<(Answer, Answer)> {
+    let solution = registry::find(year, day)
+        .ok_or_else(|| anyhow::anyhow!("Solution for day {day} and year {year} is not implemented yet"))?;
+    let day = (solution.build)(input)?;
+    let part_1 = day.part_1();
+    let part_2 = day.part_2();
+    Ok((part_1, part_2))
+}
@@ -1,12 +1,23 @@
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use aoc::answer::Answer;
+use aoc::answers::AnswerStore;
+use aoc::bench::{self, Baseline, BenchOptions};
+use aoc::memory::{self, Bytes, MemoryReport, Snapshot, StepMemory};
+use aoc::registry::{self, Solution};
+use aoc::report::{self, BenchRecord, PartRecord, Record, SolutionRecord, VerifyRecord};
+use aoc::runner::{self, DayRun};
+use rayon::prelude::*;
 use aoc::traits::Day;
-use aoc::year2023;
-use aoc::year2024;
-use aoc::year2025;
-use std::time::Instant;
-
-const VALID_YEARS: [u32; 3] = [2023, 2024, 2025];
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
 
 /// Advent of code solutions
 #[derive(Parser, Debug)]
@@ -23,114 +34,535 @@ struct Args {
     /// Part 1 or 2
     #[arg(short, long, default_value_t = 0)]
     part: u32,
+
+    /// Input file, or `-` to read from stdin. Defaults to data/year<YEAR>/day<DAY>
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Report memory before and after parsing and the peak of each part
+    #[arg(short, long)]
+    memory: bool,
+
+    /// Output format. With json or csv, stdout only carries the results
+    #[arg(long, value_enum, global = true, default_value_t = Format::Text)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Prints human-readable output, moved to stderr when stdout carries JSON or CSV.
+    fn note(self, line: impl Display) {
+        if self == Format::Text {
+            println!("{line}");
+        } else {
+            eprintln!("{line}");
+        }
+    }
+
+    /// Prints `records` in a machine-readable format; the text format prints its own tables.
+    fn emit<R: Record>(self, records: &[R]) {
+        match self {
+            Format::Text => {}
+            Format::Json => println!("{}", report::json(records)),
+            Format::Csv => print!("{}", report::csv(records)),
+        }
+    }
 }
 
-fn main() {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List all implemented solutions
+    List,
+    /// Run every day of a year, or of all years, and print a timing table
+    Run {
+        /// Year to run
+        #[arg(short, long, required_unless_present = "all")]
+        year: Option<u32>,
+
+        /// Run every year
+        #[arg(long, conflicts_with = "year")]
+        all: bool,
+
+        /// Run the days in parallel
+        #[arg(long)]
+        parallel: bool,
+
+        /// Add the memory after parsing and the peak of each part to the table
+        #[arg(short, long, conflicts_with = "parallel")]
+        memory: bool,
+    },
+    /// Benchmark a day over repeated runs
+    Bench {
+        /// Year of challenge
+        #[arg(short, long)]
+        year: u32,
+
+        /// Day of the challenge
+        #[arg(short, long)]
+        day: u32,
+
+        /// Input file, or `-` to read from stdin. Defaults to data/year<YEAR>/day<DAY>
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Number of timed runs
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+
+        /// Keep running for at least this many seconds
+        #[arg(short, long)]
+        time: Option<f64>,
+
+        /// Untimed runs before measuring
+        #[arg(short, long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Save the results into this baseline file
+        #[arg(long)]
+        save: Option<String>,
+
+        /// Compare the results against this baseline file
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Slowdown of the median, in percent, reported as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Report the memory of one more run after the timed ones
+        #[arg(short, long)]
+        memory: bool,
+    },
+    /// Check solutions against the recorded answers
+    Verify {
+        /// Only verify this year
+        #[arg(short, long)]
+        year: Option<u32>,
+
+        /// Only verify this day
+        #[arg(short, long)]
+        day: Option<u32>,
+
+        /// Record the answers that are missing from the answers file
+        #[arg(long)]
+        record: bool,
+
+        /// Answers file
+        #[arg(long, default_value = "data/answers.toml")]
+        answers: String,
+    },
+}
+
+/// Reads the puzzle input, returning the name it should be reported under and its contents.
+fn read_input(input: Option<&str>, solution: &Solution) -> Result<(String, String)> {
+    match input {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .context("Cannot read input from stdin")?;
+            Ok(("<stdin>".to_string(), input))
+        }
+        Some(path) => {
+            let input = fs::read_to_string(path)
+                .with_context(|| format!("Cannot read input file {path}"))?;
+            Ok((path.to_string(), input))
+        }
+        None => {
+            let path = solution.data_path();
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("Cannot read input file {path}"))?;
+            Ok((path, input))
+        }
+    }
+}
+
+fn main() -> Result<()> {
     let args = Args::parse();
-    if !VALID_YEARS.contains(&args.year) {
-        panic!("Please enter a valid year. Valid: {:?}", VALID_YEARS);
+    match &args.command {
+        Some(Command::List) => {
+            list(args.format);
+            return Ok(());
+        }
+        Some(Command::Run { year, parallel, memory, .. }) => {
+            return run_all(*year, *parallel, *memory, args.format);
+        }
+        Some(Command::Bench { year, day, input, runs, time, warmup, save, baseline, threshold, memory }) => {
+            let options = BenchOptions {
+                warmup: *warmup,
+                runs: *runs,
+                budget: time.map(Duration::from_secs_f64),
+            };
+            let baseline = baseline.as_deref().map(|path| (path, *threshold));
+            let solution = solution(*year, *day)?;
+            let (name, input) = read_input(input.as_deref(), solution)?;
+            bench_day(solution, &name, &input, &options, save.as_deref(), baseline, args.format)?;
+            if *memory {
+                let run = runner::run(solution, &name, &input, true)?;
+                print_memory(&run.memory.expect("Memory is tracked"), args.format);
+            }
+            return Ok(());
+        }
+        Some(Command::Verify { year, day, record, answers }) => {
+            return verify(*year, *day, *record, answers, args.format);
+        }
+        None => {}
+    }
+
+    let years = registry::years();
+    if !years.contains(&args.year) {
+        bail!("Please enter a valid year. Valid: {:?}", years);
     }
 
     if args.day < 1 || args.day > 25 {
-        panic!("Valid days should be between 1 to 25");
+        bail!("Valid days should be between 1 to 25");
     }
 
     if args.part > 2 {
-        panic!("Valid part can only be 1 or 2");
+        bail!("Valid part can only be 1 or 2");
+    }
+    let solution = registry::find(args.year, args.day).with_context(|| {
+        format!("Solution for day {} and year {} is not implemented yet", args.day, args.year)
+    })?;
+
+    let format = args.format;
+    let (name, input) = read_input(args.input.as_deref(), solution)?;
+    let before_parse = args.memory.then(Snapshot::now);
+    if let Some(snapshot) = before_parse {
+        format.note(format_args!("Memory before parse = {snapshot}"));
     }
     let start = Instant::now();
-    
-    let mut day: Box<dyn Day> = match (args.year, args.day) {
-        (2023, 1) => Box::new(year2023::day1::Day1::new()),
-        (2023, 2) => Box::new(year2023::day2::Day2::new()),
-        (2023, 3) => Box::new(year2023::day3::Day3::new()),
-        (2023, 4) => Box::new(year2023::day4::Day4::new()),
-        (2023, 5) => Box::new(year2023::day5::Day5::new()),
-        (2023, 6) => Box::new(year2023::day6::Day6::new()),
-        (2023, 7) => Box::new(year2023::day7::Day7::new()),
-        (2023, 8) => Box::new(year2023::day8::Day8::new()),
-        (2023, 9) => Box::new(year2023::day9::Day9::new()),
-        (2023, 10) => Box::new(year2023::day10::Day10::new()),
-        (2023, 11) => Box::new(year2023::day11::Day11::new()),
-        (2023, 12) => Box::new(year2023::day12::Day12::new()),
-        (2023, 13) => Box::new(year2023::day13::Day13::new()),
-        (2023, 14) => Box::new(year2023::day14::Day14::new()),
-        (2023, 15) => Box::new(year2023::day15::Day15::new()),
-        (2023, 16) => Box::new(year2023::day16::Day16::new()),
-        (2023, 17) => Box::new(year2023::day17::Day17::new()),
-        (2023, 18) => Box::new(year2023::day18::Day18::new()),
-        (2023, 19) => Box::new(year2023::day19::Day19::new()),
-        (2023, 20) => Box::new(year2023::day20::Day20::new()),
-        (2023, 21) => Box::new(year2023::day21::Day21::new()),
-        (2023, 22) => Box::new(year2023::day22::Day22::new()),
-        (2023, 23) => Box::new(year2023::day23::Day23::new()),
-        (2023, 24) => Box::new(year2023::day24::Day24::new()),
-        (2023, 25) => Box::new(year2023::day25::Day25::new()),
-
-        (2024, 1) => Box::new(year2024::day1::Day1::new()),
-        (2024, 2) => Box::new(year2024::day2::Day2::new()),
-        (2024, 3) => Box::new(year2024::day3::Day3::new()),
-        (2024, 4) => Box::new(year2024::day4::Day4::new()),
-        (2024, 5) => Box::new(year2024::day5::Day5::new()),
-        (2024, 6) => Box::new(year2024::day6::Day6::new()),
-        (2024, 7) => Box::new(year2024::day7::Day7::new()),
-        (2024, 8) => Box::new(year2024::day8::Day8::new()),
-        (2024, 9) => Box::new(year2024::day9::Day9::new()),
-        (2024, 10) => Box::new(year2024::day10::Day10::new()),
-        (2024, 11) => Box::new(year2024::day11::Day11::new()),
-        (2024, 12) => Box::new(year2024::day12::Day12::new()),
-        (2024, 13) => Box::new(year2024::day13::Day13::new()),
-        (2024, 14) => Box::new(year2024::day14::Day14::new()),
-        (2024, 15) => Box::new(year2024::day15::Day15::new()),
-        (2024, 16) => Box::new(year2024::day16::Day16::new()),
-        (2024, 17) => Box::new(year2024::day17::Day17::new()),
-        (2024, 18) => Box::new(year2024::day18::Day18::new()),
-        (2024, 19) => Box::new(year2024::day19::Day19::new()),
-        (2024, 20) => Box::new(year2024::day20::Day20::new()),
-        (2024, 21) => Box::new(year2024::day21::Day21::new()),
-        (2024, 22) => Box::new(year2024::day22::Day22::new()),
-        (2024, 23) => Box::new(year2024::day23::Day23::new()),
-        (2024, 24) => Box::new(year2024::day24::Day24::new()),
-        (2024, 25) => Box::new(year2024::day25::Day25::new()),
-
-        (2025, 1) => Box::new(year2025::day1::Day1::new()),
-        (2025, 2) => Box::new(year2025::day2::Day2::new()),
-        (2025, 3) => Box::new(year2025::day3::Day3::new()),
-        (2025, 4) => Box::new(year2025::day4::Day4::new()),
-        (2025, 5) => Box::new(year2025::day5::Day5::new()),
-        (2025, 6) => Box::new(year2025::day6::Day6::new()),
-        (2025, 7) => Box::new(year2025::day7::Day7::new()),
-        (2025, 8) => Box::new(year2025::day8::Day8::new()),
-        (2025, 9) => Box::new(year2025::day9::Day9::new()),
-        (2025, 10) => Box::new(year2025::day10::Day10::new()),
-        (2025, 11) => Box::new(year2025::day11::Day11::new()),
-
-        _ => panic!("Solution for day {} and year {} is not implemented yet", args.day, args.year),
+    let day = (solution.build)(&input).map_err(|err| err.with_file(name))?;
+    let parse = start.elapsed();
+    format.note(format_args!("Data loading = {:?}", parse));
+    let after_parse = args.memory.then(Snapshot::now);
+    if let Some(snapshot) = after_parse {
+        format.note(format_args!("Memory after parse = {snapshot}"));
+    }
+
+    let parts = if args.part == 0 {
+        format.note("Running both parts:");
+        vec![1, 2]
+    } else {
+        vec![args.part]
     };
-    let duration = start.elapsed();
-    println!("Data loading = {:?}", duration);
+    let mut records = Vec::new();
+    for part in parts {
+        let (answer, duration, peak) = run_part(day.as_ref(), part, args.memory);
+        format.note(format_args!("Solution = {} ;; Took {:?}", answer, duration));
+        let mut record = PartRecord::new(
+            solution.year,
+            solution.day,
+            part,
+            answer,
+            parse.as_nanos() as u64,
+            duration.as_nanos() as u64,
+        );
+        if let Some(peak) = peak {
+            format.note(format_args!("Peak memory = {peak}"));
+            record = record.with_memory(&peak);
+            if let (Some(before), Some(after)) = (&before_parse, &after_parse) {
+                record = record.with_parse_memory(before, after);
+            }
+        }
+        records.push(record);
+    }
+    format.emit(&records);
+    Ok(())
+}
 
-    run_part(&mut day, args.part);
+fn list(format: Format) {
+    if format != Format::Text {
+        let records: Vec<SolutionRecord> = registry::solutions()
+            .map(|s| SolutionRecord { year: s.year, day: s.day })
+            .collect();
+        format.emit(&records);
+        return;
+    }
+    for year in registry::years() {
+        let days: Vec<String> = registry::solutions()
+            .filter(|s| s.year == year)
+            .map(|s| s.day.to_string())
+            .collect();
+        println!("{year}: {}", days.join(" "));
+    }
 }
 
-fn run_part(day: &mut Box<dyn Day>, part: u32) {
-    let mut solution_fn: Box<dyn FnMut() -> u64> = match part {
-        1 => Box::new(move || day.part_1()),
-        2 => Box::new(move || day.part_2()),
-        0 => {
-            println!("Running both parts:");
-            run_part(day, 1);
-            run_part(day, 2);
-            return;
+fn print_memory(report: &MemoryReport, format: Format) {
+    format.note(format_args!("Memory before parse = {}", report.before_parse));
+    format.note(format_args!("Memory after parse = {}", report.after_parse));
+    format.note(format_args!("Part 1 peak memory = {}", report.part_1));
+    format.note(format_args!("Part 2 peak memory = {}", report.part_2));
+}
+
+fn solution(year: u32, day: u32) -> Result<&'static Solution> {
+    registry::find(year, day)
+        .with_context(|| format!("Solution for day {day} and year {year} is not implemented yet"))
+}
+
+/// Reads the default input of `solution` and runs both parts.
+fn run_default(solution: &Solution, track_memory: bool) -> Result<DayRun> {
+    let path = solution.data_path();
+    let input = fs::read_to_string(&path).with_context(|| format!("Cannot read input file {path}"))?;
+    runner::run(solution, &path, &input, track_memory)
+}
+
+/// Registered solutions, narrowed down to `year` and `day` when given.
+fn selected(year: Option<u32>, day: Option<u32>) -> Result<Vec<&'static Solution>> {
+    let solutions: Vec<&Solution> = registry::solutions()
+        .filter(|s| year.is_none_or(|y| s.year == y) && day.is_none_or(|d| s.day == d))
+        .collect();
+    if solutions.is_empty() {
+        bail!("No implemented solution matches the selection");
+    }
+    Ok(solutions)
+}
+
+fn run_all(year: Option<u32>, parallel: bool, memory: bool, format: Format) -> Result<()> {
+    let solutions = selected(year, None)?;
+    let start = Instant::now();
+    let runs: Vec<Option<Result<DayRun>>> = if parallel {
+        solutions.par_iter().map(|s| run_if_present(s, false)).collect()
+    } else {
+        solutions.iter().map(|s| run_if_present(s, memory)).collect()
+    };
+    let wall = start.elapsed();
+
+    if format != Format::Text {
+        let mut records = Vec::new();
+        let mut failed = 0;
+        for (solution, run) in solutions.iter().zip(runs) {
+            match run {
+                Some(Ok(run)) => records.extend(PartRecord::from_run(&run)),
+                Some(Err(err)) => {
+                    failed += 1;
+                    eprintln!("{} day {}: {err:#}", solution.year, solution.day);
+                }
+                None => eprintln!("{} day {}: no input", solution.year, solution.day),
+            }
+        }
+        format.emit(&records);
+        if failed > 0 {
+            bail!("{failed} days failed to run");
         }
-        _ => panic!("Invalid part: {}. Part must be 1, 2, or 0 for both.", part),
+        return Ok(());
+    }
+
+    let memory_header = if memory {
+        format!(" {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}", "Before", "Before V", "Parsed", "Parsed V", "Peak 1", "Peak 2")
+    } else {
+        String::new()
     };
-    let start = Instant::now();
-    let result = solution_fn();
-    let duration = start.elapsed();
-    
     println!(
-        "Solution = {} ;; Took {:?}",
-        result, duration
+        "{:<4} {:>3} {:>12} {:>12} {:>12} {:>12}{memory_header}  {:<16} Answer 2",
+        "Year", "Day", "Parse", "Part 1", "Part 2", "Total", "Answer 1"
+    );
+    let (mut parse, mut part_1, mut part_2, mut failed) = (Duration::ZERO, Duration::ZERO, Duration::ZERO, 0);
+    for (solution, run) in solutions.iter().zip(runs) {
+        match run {
+            Some(Ok(run)) => {
+                let memory_columns = run.memory.map_or_else(String::new, |m| {
+                    format!(
+                        " {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                        Bytes(m.before_parse.physical_mem),
+                        Bytes(m.before_parse.virtual_mem),
+                        Bytes(m.after_parse.physical_mem),
+                        Bytes(m.after_parse.virtual_mem),
+                        Bytes(m.part_1.peak.physical_mem),
+                        Bytes(m.part_2.peak.physical_mem)
+                    )
+                });
+                println!(
+                    "{:<4} {:>3} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}{memory_columns}  {:<16} {}",
+                    run.year, run.day, run.parse, run.part_1.1, run.part_2.1, run.total(), run.part_1.0.to_string(), run.part_2.0
+                );
+                parse += run.parse;
+                part_1 += run.part_1.1;
+                part_2 += run.part_2.1;
+            }
+            Some(Err(err)) => {
+                failed += 1;
+                println!("{:<4} {:>3} error: {err:#}", solution.year, solution.day);
+            }
+            None => println!("{:<4} {:>3} no input", solution.year, solution.day),
+        }
+    }
+    println!(
+        "{:<8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+        "Total", parse, part_1, part_2, parse + part_1 + part_2
     );
+    if parallel {
+        println!("Wall clock {wall:.2?}");
+    }
+
+    if failed > 0 {
+        bail!("{failed} days failed to run");
+    }
+    Ok(())
+}
+
+/// Runs `solution` against its default input, or returns `None` when there is no input file.
+fn run_if_present(solution: &Solution, track_memory: bool) -> Option<Result<DayRun>> {
+    Path::new(&solution.data_path())
+        .exists()
+        .then(|| run_default(solution, track_memory))
+}
+
+fn bench_day(
+    solution: &Solution,
+    name: &str,
+    input: &str,
+    options: &BenchOptions,
+    save: Option<&str>,
+    baseline: Option<(&str, f64)>,
+    format: Format,
+) -> Result<()> {
+    let (year, day) = (solution.year, solution.day);
+    let result = bench::bench(solution, name, input, options)?;
+    if format == Format::Text {
+        println!("{year} day {day}: {} runs", result.parse.runs);
+        println!("{:<8} {:>12} {:>12} {:>12} {:>12} {:>12}", "", "min", "median", "mean", "p95", "stddev");
+        for (step, stats) in result.steps() {
+            println!(
+                "{:<8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+                step, stats.min, stats.median, stats.mean, stats.p95, stats.stddev
+            );
+        }
+    }
+    format.emit(&BenchRecord::from_bench(year, day, &result));
+
+    let mut regressions = 0;
+    let threshold = baseline.map_or(0.0, |(_, threshold)| threshold);
+    if let Some((path, threshold)) = baseline {
+        let baseline = Baseline::load(path)?;
+        let before = baseline
+            .get(year, day)
+            .with_context(|| format!("{path} has no baseline for {year} day {day}"))?;
+        format.note(format_args!("Median against {path}:"));
+        for comparison in bench::compare(before, &result, threshold / 100.0) {
+            format.note(format_args!(
+                "{:<8} {:>12.2?} -> {:>12.2?} {:>+8.1}%{}",
+                comparison.step,
+                comparison.before,
+                comparison.after,
+                comparison.change * 100.0,
+                if comparison.regressed { "  REGRESSION" } else { "" }
+            ));
+            regressions += comparison.regressed as usize;
+        }
+    }
+
+    if let Some(path) = save {
+        let mut baseline = Baseline::load(path)?;
+        baseline.insert(year, day, result);
+        baseline.save(path)?;
+    }
+
+    if regressions > 0 {
+        bail!("{regressions} steps are more than {threshold}% slower than the baseline");
+    }
+    Ok(())
+}
+
+fn verify(year: Option<u32>, day: Option<u32>, record: bool, answers: &str, format: Format) -> Result<()> {
+    let mut store = AnswerStore::load(answers)?;
+    let solutions = selected(year, day)?;
+
+    let (mut passed, mut failed, mut missing, mut recorded) = (0, 0, 0, 0);
+    let mut records = Vec::new();
+    for solution in solutions {
+        let name = format!("{} day {:>2}", solution.year, solution.day);
+        let outcome = |part, status, answer, expected| VerifyRecord {
+            year: solution.year,
+            day: solution.day,
+            part,
+            status,
+            answer,
+            expected,
+        };
+        let (part_1, part_2) = match run_if_present(solution, false) {
+            Some(Ok(run)) => (run.part_1.0, run.part_2.0),
+            Some(Err(err)) => {
+                format.note(format_args!("{name}       : ERROR {err:#}"));
+                failed += 2;
+                records.extend([1, 2].map(|part| outcome(part, "error", None, None)));
+                continue;
+            }
+            None => {
+                format.note(format_args!("{name}       : no input"));
+                missing += 2;
+                records.extend([1, 2].map(|part| outcome(part, "no input", None, None)));
+                continue;
+            }
+        };
+
+        for (part, answer) in [(1, part_1), (2, part_2)] {
+            let got = answer.to_string();
+            let expected = store.get(solution.year, solution.day, part).map(String::from);
+            let status = match &expected {
+                Some(expected) if *expected == got => {
+                    passed += 1;
+                    format.note(format_args!("{name} part {part}: pass"));
+                    "pass"
+                }
+                Some(expected) => {
+                    failed += 1;
+                    format.note(format_args!("{name} part {part}: FAIL expected {expected}, got {got}"));
+                    "fail"
+                }
+                None if record => {
+                    recorded += 1;
+                    store.insert(solution.year, solution.day, part, &answer);
+                    format.note(format_args!("{name} part {part}: recorded {got}"));
+                    "recorded"
+                }
+                None => {
+                    missing += 1;
+                    format.note(format_args!("{name} part {part}: missing, got {got}"));
+                    "missing"
+                }
+            };
+            records.push(outcome(part, status, Some(answer), expected));
+        }
+    }
+
+    if recorded > 0 {
+        store.save(answers)?;
+    }
+    format.emit(&records);
+    format.note(format_args!("{passed} passed, {failed} failed, {missing} missing, {recorded} recorded"));
+    if failed > 0 {
+        bail!("Verification failed: {failed} mismatched answers");
+    }
+    Ok(())
+}
+
+/// Solves one part, timing it and tracking its memory when `memory` is set.
+fn run_part(day: &dyn Day, part: u32, memory: bool) -> (Answer, Duration, Option<StepMemory>) {
+    let solution_fn: Box<dyn Fn() -> Answer> = match part {
+        1 => Box::new(move || day.part_1()),
+        2 => Box::new(move || day.part_2()),
+        _ => panic!("Invalid part: {}. Part must be 1 or 2.", part),
+    };
+    let timed = || {
+        let start = Instant::now();
+        let result = solution_fn();
+        (result, start.elapsed())
+    };
+    if memory {
+        let ((result, duration), peak) = memory::track(timed);
+        (result, duration, Some(peak))
+    } else {
+        let (result, duration) = timed();
+        (result, duration, None)
+    }
 }
\ No newline at end of file
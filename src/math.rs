@@ -0,0 +1,374 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The primitive integer types, so gcd and lcm work on whichever a puzzle uses.
+pub trait Integer:
+    Copy + Ord + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),+) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })+
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, never negative. 0 when either is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// Least common multiple of all of `values`, 1 when there are none.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+fn abs<T: Integer>(n: T) -> T {
+    if n < T::ZERO { T::ZERO - n } else { n }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+/// `extended_gcd` on `i128`, for working values that outgrow an `i64`.
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power `exp`, modulo `m`.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Chinese remainder theorem: the `x` satisfying `x = r (mod m)` for every `(r, m)`, as
+/// `(x, l)` where `l` is the lcm of the moduli and `x` is in `0..l`. The moduli need not be
+/// coprime. `None` when the congruences contradict each other or a modulus is not positive.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut l) = (0_i128, 1_i128);
+    for (r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let (r, m) = (r as i128, m as i128);
+        let (g, p, _) = extended_gcd_wide(l, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        // x + l * k = r (mod m), so k = (r - x) / g * p (mod m / g).
+        let step = m / g;
+        let k = ((r - x) / g % step * p % step).rem_euclid(step);
+        x += l * k;
+        l *= step;
+        x = x.rem_euclid(l);
+    }
+    let fits = "the combined modulus does not fit in an i64";
+    Some((x.try_into().expect(fits), l.try_into().expect(fits)))
+}
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// `num / den`. Panics when `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "{num}/0 is not a number");
+        let g = gcd(num, den) * den.signum();
+        Rational { num: num / g, den: den / g }
+    }
+
+    pub const fn integer(n: i128) -> Self {
+        Rational { num: n, den: 1 }
+    }
+
+    pub const fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub const fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub const fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub const fn to_integer(&self) -> Option<i128> {
+        if self.is_integer() { Some(self.num) } else { None }
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.den, self.num)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::integer(n as i128)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational::integer(n)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        let g = gcd(self.den, other.den);
+        Rational::new(self.num * (other.den / g) + other.num * (self.den / g), self.den / g * other.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        product(self, other)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        product(self, other.recip())
+    }
+}
+
+fn product(a: Rational, b: Rational) -> Rational {
+    // Cancel across first so the products stay small.
+    let x = Rational::new(a.num, b.den);
+    let y = Rational::new(b.num, a.den);
+    Rational::new(x.num * y.num, x.den * y.den)
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self - *other).num.cmp(&0)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Solves `a * [x, y] = b` exactly. `None` when `a` is singular.
+pub fn solve_2x2(a: [[i64; 2]; 2], b: [i64; 2]) -> Option<[Rational; 2]> {
+    let [[a0, a1], [a2, a3]] = a.map(|row| row.map(i128::from));
+    let [b0, b1] = b.map(i128::from);
+    let det = a0 * a3 - a1 * a2;
+    if det == 0 {
+        return None;
+    }
+    Some([Rational::new(b0 * a3 - a1 * b1, det), Rational::new(a0 * b1 - b0 * a2, det)])
+}
+
+/// Like `solve_2x2`, only accepting a solution in whole numbers.
+pub fn solve_2x2_integer(a: [[i64; 2]; 2], b: [i64; 2]) -> Option<[i64; 2]> {
+    let [x, y] = solve_2x2(a, b)?;
+    Some([x.to_integer()?.try_into().ok()?, y.to_integer()?.try_into().ok()?])
+}
+
+/// Solves the square system `a * x = b` exactly by Gauss-Jordan elimination. `None` when
+/// `a` is singular, so there is no unique solution.
+pub fn solve_linear(mut a: Vec<Vec<Rational>>, mut b: Vec<Rational>) -> Option<Vec<Rational>> {
+    let n = b.len();
+    assert!(a.len() == n && a.iter().all(|row| row.len() == n), "solve_linear needs an n x n matrix");
+    for col in 0..n {
+        let pivot = (col..n).find(|&row| !a[row][col].is_zero())?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let scale = a[col][col].recip();
+        for value in &mut a[col][col..] {
+            *value = *value * scale;
+        }
+        b[col] = b[col] * scale;
+        let pivot_row = a[col].clone();
+        for row in 0..n {
+            let factor = a[row][col];
+            if row == col || factor.is_zero() {
+                continue;
+            }
+            for (value, &pivot) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *value = *value - factor * pivot;
+            }
+            b[row] = b[row] - factor * b[col];
+        }
+    }
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12_i64, 18), 6);
+        assert_eq!(gcd(0_u32, 5), 5);
+        assert_eq!(lcm(4_u64, 6), 12);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<i32>::new()), 1);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli sharing factors, consistent and not.
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 3), (0, 0)]), None);
+        assert_eq!(crt([(1, -4)]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn test_crt_overflow() {
+        // The combined modulus outgrows an i64 and has to be reported, not truncated.
+        let (p, q) = (10_000_000_019, 10_000_000_033);
+        crt([(1, p), (2, q), (1, p)]);
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(3, -6), -half);
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - Rational::ONE, Rational::new(-1, 2));
+        assert_eq!(half * Rational::from(4_i64), Rational::integer(2));
+        assert_eq!(half / Rational::new(1, 4), Rational::integer(2));
+        assert!(Rational::new(1, 3) < half);
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Rational::new(-7, 2).to_string(), "-7/2");
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve_2x2_integer([[94, 22], [34, 67]], [8400, 5400]), Some([80, 40]));
+        assert_eq!(solve_2x2_integer([[26, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+        assert_eq!(solve_2x2([[2, 0], [0, 4]], [1, 1]), Some([Rational::new(1, 2), Rational::new(1, 4)]));
+
+        let a = [[2, 1, -1], [-3, -1, 2], [-2, 1, 2]];
+        let a = a.iter().map(|row| row.iter().map(|&v| Rational::from(v as i64)).collect()).collect();
+        let b = [8, -11, -3].iter().map(|&v| Rational::from(v as i64)).collect();
+        let x = solve_linear(a, b).unwrap();
+        assert_eq!(x, vec![Rational::integer(2), Rational::integer(3), Rational::integer(-1)]);
+
+        let singular = vec![vec![Rational::ONE, Rational::ONE], vec![Rational::ONE, Rational::ONE]];
+        assert_eq!(solve_linear(singular, vec![Rational::ONE, Rational::ZERO]), None);
+    }
+}
@@ -0,0 +1,214 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use memory_stats::memory_stats;
+
+/// Memory of the whole process, as reported by the OS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub physical_mem: usize,
+    pub virtual_mem: usize,
+}
+
+impl Snapshot {
+    /// Current usage, or zero on platforms `memory-stats` does not support.
+    pub fn now() -> Self {
+        memory_stats().map_or_else(Snapshot::default, |stats| Snapshot {
+            physical_mem: stats.physical_mem,
+            virtual_mem: stats.virtual_mem,
+        })
+    }
+
+    fn max(self, other: Snapshot) -> Snapshot {
+        Snapshot {
+            physical_mem: self.physical_mem.max(other.physical_mem),
+            virtual_mem: self.virtual_mem.max(other.virtual_mem),
+        }
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} physical, {} virtual", Bytes(self.physical_mem), Bytes(self.virtual_mem))
+    }
+}
+
+/// Polls the process memory on a background thread to catch the peak of a step.
+///
+/// The OS only reports current usage, so a peak shorter than the polling interval can be missed.
+struct PeakSampler {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<Snapshot>,
+}
+
+impl PeakSampler {
+    const INTERVAL: Duration = Duration::from_millis(1);
+
+    fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut peak = Snapshot::now();
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(Self::INTERVAL);
+                    peak = peak.max(Snapshot::now());
+                }
+                peak
+            })
+        };
+        PeakSampler { stop, handle }
+    }
+
+    fn finish(self) -> Snapshot {
+        let end = Snapshot::now();
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.join().unwrap_or_default().max(end)
+    }
+}
+
+static HEAP_CURRENT: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that keeps track of the live heap size, its peak and the number of
+/// allocations. The binary installs it when built with the `count-alloc` feature.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(size: usize) {
+        let current = HEAP_CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        HEAP_PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        HEAP_CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                HEAP_CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Whether the `CountingAllocator` is installed. Nothing allocates through it otherwise.
+pub fn counting() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+/// Heap usage of one step, only known with the `CountingAllocator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heap {
+    pub peak: usize,
+    pub allocations: usize,
+}
+
+/// Memory used while running one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepMemory {
+    pub peak: Snapshot,
+    pub heap: Option<Heap>,
+}
+
+impl fmt::Display for StepMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.peak)?;
+        if let Some(heap) = self.heap {
+            write!(f, "; heap peak {} over {} allocations", Bytes(heap.peak), heap.allocations)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `step` while tracking the peak memory of the process.
+pub fn track<T>(step: impl FnOnce() -> T) -> (T, StepMemory) {
+    let counting = counting();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    HEAP_PEAK.store(HEAP_CURRENT.load(Ordering::Relaxed), Ordering::Relaxed);
+
+    let sampler = PeakSampler::start();
+    let value = step();
+    let peak = sampler.finish();
+
+    let heap = counting.then(|| Heap {
+        peak: HEAP_PEAK.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    });
+    (value, StepMemory { peak, heap })
+}
+
+/// Memory of a full run of one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryReport {
+    pub before_parse: Snapshot,
+    pub after_parse: Snapshot,
+    pub part_1: StepMemory,
+    pub part_2: StepMemory,
+}
+
+/// A byte count, printed with a binary unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes(pub usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            f.pad(&format!("{} B", self.0))
+        } else {
+            f.pad(&format!("{value:.1} {}", UNITS[unit]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::Bytes;
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 << 30).to_string(), "3.0 GiB");
+        assert_eq!(format!("{:>9}", Bytes(10 << 20)), " 10.0 MiB");
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+/// Error raised when a puzzle input does not have the expected shape.
+///
+/// `line` and `column` are 1-based and point at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `at`, which should be a slice of `input`.
+    ///
+    /// When `at` is not borrowed from `input` the first occurrence of it is
+    /// reported instead.
+    pub fn at(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let base = input.as_ptr() as usize;
+        let ptr = at.as_ptr() as usize;
+        let offset = if ptr >= base && ptr + at.len() <= base + input.len() {
+            ptr - base
+        } else {
+            input.find(at).unwrap_or(input.len())
+        };
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        ParseError {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: at.to_string(),
+            expected: expected.into(),
+            source_line: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        if self.text.is_empty() {
+            writeln!(f, "expected {}, found nothing", self.expected)?;
+        } else {
+            writeln!(f, "expected {}, found `{}`", self.expected, self.text)?;
+        }
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        writeln!(f, "{pad}--> {file}:{}:{}", self.line, self.column)?;
+        writeln!(f, "{pad} |")?;
+        writeln!(f, "{number} | {}", self.source_line)?;
+        let width = self.text.lines().next().map_or(0, |l| l.chars().count()).max(1);
+        write!(f, "{pad} | {}{}", " ".repeat(self.column - 1), "^".repeat(width))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `s`, a slice of `input`, into a number.
+pub fn number<T: FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::at(input, s, "a number"))
+}
+
+/// Splits `s`, a slice of `input`, around the first `delimiter`.
+pub fn split_once<'a>(
+    input: &str,
+    s: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, s, format!("`{delimiter}`")))
+}
+
+/// Unwraps a value taken out of `context`, reporting the whole context when it is missing.
+pub fn expect<T>(
+    input: &str,
+    value: Option<T>,
+    context: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    value.ok_or_else(|| ParseError::at(input, context, expected))
+}
+
+/// Splits `s`, a slice of `input`, into the rows of a rectangular grid made
+/// only of `allowed` characters.
+pub fn grid<'a>(input: &str, s: &'a str, allowed: &str) -> Result<Vec<&'a str>, ParseError> {
+    let rows: Vec<&str> = s.lines().collect();
+    let Some(width) = rows.first().map(|row| row.chars().count()) else {
+        return Err(ParseError::at(input, s, "a grid"));
+    };
+    for row in &rows {
+        if let Some((i, c)) = row.char_indices().find(|&(_, c)| !allowed.contains(c)) {
+            return Err(ParseError::at(input, &row[i..i + c.len_utf8()], format!("one of `{allowed}`")));
+        }
+        if row.chars().count() != width {
+            return Err(ParseError::at(input, row, format!("a row of width {width}")));
+        }
+    }
+    Ok(rows)
+}
+
+/// Reads `path` and parses it, panicking with the rendered error on failure.
+pub fn load<T>(path: &str, parse: fn(&str) -> Result<T, ParseError>) -> T {
+    let input = fs::read_to_string(path).unwrap_or_else(|err| panic!("Cannot read {path}: {err}"));
+    parse(&input).unwrap_or_else(|err| panic!("{}", err.with_file(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{self, ParseError};
+
+    #[test]
+    fn test_error_position() {
+        let input = "1 2\n3 x4\n";
+        let at = &input[6..8];
+        let err = ParseError::at(input, at, "a number");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "x4");
+
+        let err = parse::number::<u32>(input, at).unwrap_err().with_file("day1");
+        assert_eq!(
+            err.to_string(),
+            "expected a number, found `x4`\n --> day1:2:3\n  |\n2 | 3 x4\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn test_split_once() {
+        let input = "a: b\nc";
+        assert_eq!(parse::split_once(input, &input[..4], ": "), Ok(("a", "b")));
+        let err = parse::split_once(input, &input[5..], ": ").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 1, "`: `"));
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "#.#\n.#.\n";
+        assert_eq!(parse::grid(input, input, ".#"), Ok(vec!["#.#", ".#."]));
+
+        let input = "#.#\n.x.";
+        let err = parse::grid(input, input, ".#").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        let input = "#.#\n.#";
+        let err = parse::grid(input, input, ".#").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row of width 3"));
+    }
+}
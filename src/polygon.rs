@@ -0,0 +1,262 @@
+use crate::geom::Direction;
+use crate::math;
+
+/// A closed polygon on the integer lattice, running through its vertices in order and
+/// back from the last to the first. Either winding works.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<(i64, i64)>,
+}
+
+impl Polygon {
+    pub fn new(vertices: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        Polygon { vertices: vertices.into_iter().collect() }
+    }
+
+    /// The polygon traced by walking each `(direction, distance)` in turn from `start`.
+    pub fn from_moves(start: (i64, i64), moves: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut at = start;
+        let mut vertices = vec![start];
+        for (direction, distance) in moves {
+            let step = direction.offset();
+            at = (at.0 + step.x as i64 * distance, at.1 + step.y as i64 * distance);
+            vertices.push(at);
+        }
+        if vertices.len() > 1 && vertices.last() == Some(&start) {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[(i64, i64)] {
+        &self.vertices
+    }
+
+    /// Each side, from one vertex to the next.
+    pub fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the enclosed area, by the shoelace formula. Always a whole number.
+    pub fn double_area(&self) -> u128 {
+        let sum: i128 = self.edges().map(|(a, b)| a.0 as i128 * b.1 as i128 - b.0 as i128 * a.1 as i128).sum();
+        sum.unsigned_abs()
+    }
+
+    /// The enclosed area, rounded down when it ends in a half.
+    pub fn area(&self) -> u64 {
+        (self.double_area() / 2) as u64
+    }
+
+    /// How many lattice points lie on the sides, which is the perimeter when every side is
+    /// horizontal or vertical.
+    pub fn boundary_len(&self) -> u64 {
+        self.edges().map(|(a, b)| math::gcd(b.0 - a.0, b.1 - a.1) as u64).sum()
+    }
+
+    /// How many lattice points lie strictly inside, by Pick's theorem. Zero for a polygon
+    /// enclosing no area, such as a path out and back, where the theorem does not hold.
+    pub fn interior_points(&self) -> u64 {
+        ((self.double_area() + 2).saturating_sub(self.boundary_len() as u128) / 2) as u64
+    }
+
+    pub fn on_boundary(&self, point: (i64, i64)) -> bool {
+        self.edges().any(|(a, b)| {
+            let cross = (b.0 - a.0) as i128 * (point.1 - a.1) as i128 - (b.1 - a.1) as i128 * (point.0 - a.0) as i128;
+            cross == 0
+                && a.0.min(b.0) <= point.0 && point.0 <= a.0.max(b.0)
+                && a.1.min(b.1) <= point.1 && point.1 <= a.1.max(b.1)
+        })
+    }
+
+    /// Whether `point` is inside or on the boundary. Counts the sides crossed by a ray
+    /// running right from `point`, so it works for any simple polygon.
+    pub fn contains(&self, point: (i64, i64)) -> bool {
+        if self.on_boundary(point) {
+            return true;
+        }
+        let crossings = self
+            .edges()
+            .filter(|&(a, b)| (a.1 > point.1) != (b.1 > point.1))
+            .filter(|&(a, b)| {
+                // The side meets the ray's line right of `point`, compared without dividing.
+                let (dx, dy) = ((b.0 - a.0) as i128, (b.1 - a.1) as i128);
+                let lhs = (point.0 - a.0) as i128 * dy;
+                let rhs = (point.1 - a.1) as i128 * dx;
+                if dy > 0 { lhs < rhs } else { lhs > rhs }
+            })
+            .count();
+        crossings % 2 == 1
+    }
+}
+
+/// A polygon whose sides are all horizontal or vertical, cut into cells along the lines
+/// through its vertices so that points and whole rectangles are checked with a few lookups.
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// `outside[y][x]` counts the cells outside the polygon above and left of cell `(x, y)`.
+    outside: Vec<Vec<u32>>,
+}
+
+impl RectilinearPolygon {
+    pub fn new(polygon: &Polygon) -> Self {
+        let coordinates = |axis: fn(&(i64, i64)) -> i64| {
+            let mut values: Vec<i64> = polygon.vertices().iter().map(axis).collect();
+            values.sort_unstable();
+            values.dedup();
+            values
+        };
+        let (xs, ys) = (coordinates(|v| v.0), coordinates(|v| v.1));
+
+        // `walls[i][j]` when a side runs along `xs[i]` between `ys[j]` and `ys[j + 1]`.
+        let mut walls = vec![vec![false; ys.len()]; xs.len()];
+        for (a, b) in polygon.edges().filter(|(a, b)| a.0 == b.0) {
+            let x = xs.binary_search(&a.0).unwrap();
+            let (top, bottom) = (ys.binary_search(&a.1.min(b.1)).unwrap(), ys.binary_search(&a.1.max(b.1)).unwrap());
+            walls[x][top..bottom].fill(true);
+        }
+        // Whether the open cell between lines `i` and `i + 1` across and `j` and `j + 1` down
+        // is inside, going by how many walls lie to its left.
+        let mut inside = vec![vec![false; ys.len()]; xs.len()];
+        for j in 0..ys.len().saturating_sub(1) {
+            let mut within = false;
+            for i in 0..xs.len().saturating_sub(1) {
+                within ^= walls[i][j];
+                inside[i][j] = within;
+            }
+        }
+        // A cell on a line is inside or on the boundary when an open cell next to it is inside.
+        let around = |index: usize, len: usize| {
+            let (first, last) = if index % 2 == 1 { (index / 2, index / 2) } else { ((index / 2).max(1) - 1, index / 2) };
+            first..=last.min(len.saturating_sub(2))
+        };
+
+        let mut outside = vec![vec![0; 2 * xs.len()]; 2 * ys.len()];
+        for row in 1..outside.len() {
+            for column in 1..outside[0].len() {
+                let (x, y) = (column - 1, row - 1);
+                let empty = cell_point(&xs, x).is_none() || cell_point(&ys, y).is_none();
+                let within = around(x, xs.len()).any(|i| around(y, ys.len()).any(|j| inside[i][j]));
+                let cell = (!empty && !within) as u32;
+                outside[row][column] = cell + outside[row - 1][column] + outside[row][column - 1] - outside[row - 1][column - 1];
+            }
+        }
+        RectilinearPolygon { xs, ys, outside }
+    }
+
+    /// Whether `point` is inside or on the boundary.
+    pub fn contains(&self, point: (i64, i64)) -> bool {
+        self.contains_rect(point, point)
+    }
+
+    /// Whether every lattice point of the rectangle with opposite corners `a` and `b` is
+    /// inside or on the boundary.
+    pub fn contains_rect(&self, a: (i64, i64), b: (i64, i64)) -> bool {
+        let (Some(left), Some(right)) = (cell(&self.xs, a.0.min(b.0)), cell(&self.xs, a.0.max(b.0))) else {
+            return false;
+        };
+        let (Some(top), Some(bottom)) = (cell(&self.ys, a.1.min(b.1)), cell(&self.ys, a.1.max(b.1))) else {
+            return false;
+        };
+        let sum = |row: usize, column: usize| self.outside[row][column] as i64;
+        sum(bottom + 1, right + 1) - sum(top, right + 1) - sum(bottom + 1, left) + sum(top, left) == 0
+    }
+}
+
+/// The cell along one axis holding `value`: cell `2i` is exactly `lines[i]` and cell
+/// `2i + 1` lies strictly between `lines[i]` and `lines[i + 1]`. `None` beyond the lines.
+fn cell(lines: &[i64], value: i64) -> Option<usize> {
+    match lines.binary_search(&value) {
+        Ok(i) => Some(2 * i),
+        Err(i) if i == 0 || i == lines.len() => None,
+        Err(i) => Some(2 * i - 1),
+    }
+}
+
+/// A lattice value inside cell `index`, if it has any.
+fn cell_point(lines: &[i64], index: usize) -> Option<i64> {
+    let i = index / 2;
+    if index.is_multiple_of(2) {
+        return Some(lines[i]);
+    }
+    (lines[i] + 1 < lines[i + 1]).then_some(lines[i] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::Direction;
+    use crate::polygon::{Polygon, RectilinearPolygon};
+
+    /// An L made of a 4 by 2 bar with a 2 by 2 block on top of its left end.
+    fn ell() -> Polygon {
+        Polygon::new([(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)])
+    }
+
+    #[test]
+    fn test_measures() {
+        let polygon = ell();
+        assert_eq!(polygon.double_area(), 24);
+        assert_eq!(polygon.area(), 12);
+        assert_eq!(polygon.boundary_len(), 16);
+        assert_eq!(polygon.interior_points(), 5);
+
+        let walked = Polygon::from_moves((0, 0), [(Direction::East, 2), (Direction::South, 2), (Direction::East, 2), (Direction::South, 2), (Direction::West, 4), (Direction::North, 4)]);
+        assert_eq!(walked, ell());
+
+        // A triangle with slanted sides, and the same one in the other direction.
+        let triangle = Polygon::new([(0, 0), (4, 0), (0, 6)]);
+        assert_eq!((triangle.double_area(), triangle.boundary_len()), (24, 12));
+        assert_eq!(triangle.interior_points(), 7);
+        assert_eq!(Polygon::new([(0, 6), (4, 0), (0, 0)]).double_area(), 24);
+
+        // Out and back along a line encloses nothing.
+        let line = Polygon::new([(0, 0), (5, 0), (5, 3), (5, 0)]);
+        assert_eq!((line.double_area(), line.boundary_len()), (0, 16));
+        assert_eq!(line.interior_points(), 0);
+    }
+
+    #[test]
+    fn test_contains() {
+        let polygon = ell();
+        assert!(polygon.contains((1, 1)) && polygon.contains((3, 3)));
+        assert!(polygon.on_boundary((2, 1)) && polygon.contains((4, 4)));
+        assert!(!polygon.contains((3, 1)) && !polygon.contains((5, 3)) && !polygon.contains((-1, 2)));
+
+        let triangle = Polygon::new([(0, 0), (4, 0), (0, 6)]);
+        assert!(triangle.contains((1, 1)) && triangle.on_boundary((2, 3)));
+        assert!(!triangle.contains((3, 2)));
+    }
+
+    #[test]
+    fn test_rectilinear() {
+        let polygon = ell();
+        let cells = RectilinearPolygon::new(&polygon);
+        for x in -1..=5 {
+            for y in -1..=5 {
+                assert_eq!(cells.contains((x, y)), polygon.contains((x, y)), "({x}, {y})");
+            }
+        }
+        assert!(cells.contains_rect((0, 0), (2, 4)));
+        assert!(cells.contains_rect((4, 2), (0, 4)));
+        assert!(!cells.contains_rect((0, 0), (3, 3)));
+        assert!(!cells.contains_rect((1, 1), (1, 5)));
+    }
+
+    #[test]
+    fn test_narrow_gap() {
+        // A U whose arms are one apart, so the gap holds no lattice points of its own.
+        let polygon = Polygon::new([(0, 0), (2, 0), (2, 3), (3, 3), (3, 0), (5, 0), (5, 5), (0, 5)]);
+        let cells = RectilinearPolygon::new(&polygon);
+        assert!(cells.contains_rect((0, 0), (5, 5)));
+        assert!(cells.contains_rect((2, 1), (3, 1)));
+    }
+
+    #[test]
+    fn test_empty() {
+        let cells = RectilinearPolygon::new(&Polygon::new([]));
+        assert!(!cells.contains((0, 0)));
+    }
+}
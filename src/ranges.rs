@@ -0,0 +1,339 @@
+use std::fmt;
+
+/// The integers from `start` up to but not including `end`. Empty when `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// The integers from `start` up to and including `end`, the way puzzles usually write ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InclusiveInterval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    /// The `len` integers starting at `start`.
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub const fn len(&self) -> u64 {
+        if self.is_empty() { 0 } else { self.end.abs_diff(self.start) }
+    }
+
+    pub const fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The integers in both intervals, which may be an empty interval.
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The parts below `at` and from `at` onwards. Either may be empty.
+    pub fn split_at(&self, at: i64) -> (Interval, Interval) {
+        let at = at.clamp(self.start, self.end.max(self.start));
+        (Interval::new(self.start, at), Interval::new(at, self.end))
+    }
+
+    /// Every integer moved by `offset`.
+    pub const fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<InclusiveInterval> for Interval {
+    fn from(interval: InclusiveInterval) -> Self {
+        Interval::new(interval.start, interval.end + 1)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+impl InclusiveInterval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        InclusiveInterval { start, end }
+    }
+
+    pub const fn len(&self) -> u64 {
+        Interval::new(self.start, self.end + 1).len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    pub const fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+}
+
+impl fmt::Display for InclusiveInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// A set of integers kept as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// The intervals making up the set, lowest first.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.end <= value);
+        self.intervals.get(i).is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: impl Into<Interval>) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        // Intervals touching the new one are merged into it.
+        let first = self.intervals.partition_point(|other| other.end < interval.start);
+        let last = self.intervals.partition_point(|other| other.start <= interval.end);
+        let touching = &self.intervals[first..last];
+        let merged = match (touching.first(), touching.last()) {
+            (Some(low), Some(high)) => Interval::new(low.start.min(interval.start), high.end.max(interval.end)),
+            _ => interval,
+        };
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            let both = a.intersection(&b);
+            if !both.is_empty() {
+                intervals.push(both);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let mut others = other.intervals.iter().peekable();
+        for &interval in &self.intervals {
+            let mut rest = interval;
+            while let Some(cut) = others.peek() {
+                if cut.end <= rest.start {
+                    others.next();
+                    continue;
+                }
+                if cut.start >= rest.end {
+                    break;
+                }
+                let (below, _) = rest.split_at(cut.start);
+                let (_, above) = rest.split_at(cut.end);
+                if !below.is_empty() {
+                    intervals.push(below);
+                }
+                rest = above;
+                if cut.end > interval.end {
+                    break;
+                }
+                others.next();
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<I: Into<Interval>> FromIterator<I> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+/// A function on the integers moving each of a list of source intervals by its own offset,
+/// and leaving every other value where it is. Where sources overlap the first one wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<(Interval, i64)>,
+}
+
+impl PiecewiseMap {
+    pub fn new() -> Self {
+        PiecewiseMap::default()
+    }
+
+    /// Moves the values in `source` by `offset`.
+    pub fn insert(&mut self, source: Interval, offset: i64) {
+        self.pieces.push((source, offset));
+    }
+
+    /// Sends the `len` values from `source` to the ones from `destination`, as the
+    /// `<destination> <source> <length>` lines of a mapping table do.
+    pub fn insert_range(&mut self, destination: i64, source: i64, len: i64) {
+        self.insert(Interval::with_len(source, len), destination - source);
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    /// The image of every value of `set`, worked out an interval at a time.
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut image = IntervalSet::new();
+        let mut rest = set.clone();
+        for &(source, offset) in &self.pieces {
+            let source = IntervalSet::from(source);
+            for interval in rest.intersection(&source).intervals() {
+                image.insert(interval.shift(offset));
+            }
+            rest = rest.difference(&source);
+        }
+        image.union(&rest)
+    }
+}
+
+impl FromIterator<(Interval, i64)> for PiecewiseMap {
+    fn from_iter<T: IntoIterator<Item = (Interval, i64)>>(iter: T) -> Self {
+        PiecewiseMap { pieces: iter.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ranges::{InclusiveInterval, Interval, IntervalSet, PiecewiseMap};
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|&(start, end)| Interval::new(start, end)).collect()
+    }
+
+    /// Every value of `set` one by one, to check the interval arithmetic against.
+    fn values(set: &IntervalSet) -> Vec<i64> {
+        set.intervals().iter().flat_map(|interval| interval.start..interval.end).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::from(InclusiveInterval::new(3, 5));
+        assert_eq!(interval, Interval::new(3, 6));
+        assert_eq!(interval.len(), 3);
+        assert_eq!(InclusiveInterval::new(3, 5).len(), 3);
+        assert!(interval.contains(5) && !interval.contains(6));
+        assert_eq!(interval.split_at(4), (Interval::new(3, 4), Interval::new(4, 6)));
+        assert_eq!(interval.split_at(10), (Interval::new(3, 6), Interval::new(6, 6)));
+        assert!(interval.intersection(&Interval::new(6, 9)).is_empty());
+        assert_eq!(Interval::new(5, 2).len(), 0);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let a = set(&[(10, 14), (0, 3), (3, 5), (20, 20)]);
+        assert_eq!(a.intervals(), &[Interval::new(0, 5), Interval::new(10, 14)]);
+        assert_eq!(a.len(), 9);
+        assert_eq!((a.min(), a.max()), (Some(0), Some(13)));
+        assert!(a.contains(4) && !a.contains(5) && a.contains(10));
+
+        let b = set(&[(2, 11), (13, 30)]);
+        assert_eq!(b.clone().union(&a), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(2, 5), (10, 11), (13, 14)]));
+        assert_eq!(a.difference(&b), set(&[(0, 2), (11, 13)]));
+        assert_eq!(b.difference(&a), set(&[(5, 10), (14, 30)]));
+
+        // The same again, one value at a time.
+        let (va, vb) = (values(&a), values(&b));
+        let both: Vec<i64> = va.iter().copied().filter(|v| vb.contains(v)).collect();
+        let only: Vec<i64> = va.iter().copied().filter(|v| !vb.contains(v)).collect();
+        assert_eq!(values(&a.intersection(&b)), both);
+        assert_eq!(values(&a.difference(&b)), only);
+    }
+
+    #[test]
+    fn test_piecewise_map() {
+        let mut map = PiecewiseMap::new();
+        map.insert_range(50, 98, 2);
+        map.insert_range(52, 50, 48);
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(10), 10);
+
+        let seeds = set(&[(40, 60), (95, 105)]);
+        let image = map.apply_set(&seeds);
+        let mut expected: Vec<i64> = values(&seeds).into_iter().map(|v| map.apply(v)).collect();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(values(&image), expected);
+    }
+}
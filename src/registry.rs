@@ -0,0 +1,79 @@
+use crate::parse::ParseError;
+use crate::traits::Day;
+use crate::{year2023, year2024, year2025};
+
+/// A registered solution, built from the puzzle input.
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub build: fn(&str) -> Result<Box<dyn Day>, ParseError>,
+}
+
+impl Solution {
+    /// Default location of the puzzle input.
+    pub fn data_path(&self) -> String {
+        format!("data/year{}/day{}", self.year, self.day)
+    }
+}
+
+/// Declares the day modules of a year and registers each of them.
+///
+/// ```ignore
+/// crate::registry::days! {
+///     year 2024;
+///     1 => day1::Day1,
+///     2 => day2::Day2,
+/// }
+/// ```
+///
+/// expands to `pub mod day1; pub mod day2;` and a `SOLUTIONS` table for the year.
+macro_rules! days {
+    (year $year:literal; $($day:literal => $module:ident :: $ty:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub const SOLUTIONS: &[$crate::registry::Solution] = &[
+            $($crate::registry::Solution {
+                year: $year,
+                day: $day,
+                build: |input| Ok(Box::new($module::$ty::parse(input)?)),
+            },)*
+        ];
+    };
+}
+
+pub(crate) use days;
+
+const YEARS: &[&[Solution]] = &[
+    year2023::SOLUTIONS,
+    year2024::SOLUTIONS,
+    year2025::SOLUTIONS,
+];
+
+/// All registered solutions, ordered by year and day.
+pub fn solutions() -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().flat_map(|year| year.iter())
+}
+
+pub fn find(year: u32, day: u32) -> Option<&'static Solution> {
+    solutions().find(|s| s.year == year && s.day == day)
+}
+
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = solutions().map(|s| s.year).collect();
+    years.dedup();
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry;
+
+    #[test]
+    fn test_registry_is_ordered_and_unique() {
+        let keys: Vec<(u32, u32)> = registry::solutions().map(|s| (s.year, s.day)).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(registry::years(), vec![2023, 2024, 2025]);
+        assert!(registry::find(2024, 17).is_some());
+        assert!(registry::find(2025, 25).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::bench::Bench;
+use crate::memory::{Snapshot, StepMemory};
+use crate::runner::DayRun;
+
+/// A row of machine-readable output.
+///
+/// JSON output goes through `Serialize`; CSV output uses `COLUMNS` and `values` so nested
+/// answers such as lists and coordinates end up in a single cell.
+pub trait Record: Serialize {
+    const COLUMNS: &'static [&'static str];
+
+    fn values(&self) -> Vec<String>;
+}
+
+/// Renders `records` as a pretty-printed JSON array.
+pub fn json<R: Record>(records: &[R]) -> String {
+    serde_json::to_string_pretty(records).expect("Records always serialize")
+}
+
+/// Renders `records` as CSV with a header row.
+pub fn csv<R: Record>(records: &[R]) -> String {
+    let mut out = R::COLUMNS.join(",");
+    out.push('\n');
+    for record in records {
+        let row: Vec<String> = record.values().iter().map(|v| csv_field(v)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(String::new, |v| v.to_string())
+}
+
+/// Answer and timings of one part, along with its memory when it was tracked.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartRecord {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+    pub before_parse_physical_mem: Option<usize>,
+    pub before_parse_virtual_mem: Option<usize>,
+    pub after_parse_physical_mem: Option<usize>,
+    pub after_parse_virtual_mem: Option<usize>,
+    pub peak_physical_mem: Option<usize>,
+    pub peak_virtual_mem: Option<usize>,
+    pub heap_peak: Option<usize>,
+    pub allocations: Option<usize>,
+}
+
+impl PartRecord {
+    /// Record without any memory figures, see `with_memory`.
+    pub fn new(year: u32, day: u32, part: u32, answer: Answer, parse_ns: u64, solve_ns: u64) -> Self {
+        PartRecord {
+            year,
+            day,
+            part,
+            answer,
+            parse_ns,
+            solve_ns,
+            before_parse_physical_mem: None,
+            before_parse_virtual_mem: None,
+            after_parse_physical_mem: None,
+            after_parse_virtual_mem: None,
+            peak_physical_mem: None,
+            peak_virtual_mem: None,
+            heap_peak: None,
+            allocations: None,
+        }
+    }
+
+    pub fn with_memory(mut self, step: &StepMemory) -> Self {
+        self.peak_physical_mem = Some(step.peak.physical_mem);
+        self.peak_virtual_mem = Some(step.peak.virtual_mem);
+        self.heap_peak = step.heap.map(|heap| heap.peak);
+        self.allocations = step.heap.map(|heap| heap.allocations);
+        self
+    }
+
+    /// Adds the memory of the process just before and just after parsing.
+    pub fn with_parse_memory(mut self, before: &Snapshot, after: &Snapshot) -> Self {
+        self.before_parse_physical_mem = Some(before.physical_mem);
+        self.before_parse_virtual_mem = Some(before.virtual_mem);
+        self.after_parse_physical_mem = Some(after.physical_mem);
+        self.after_parse_virtual_mem = Some(after.virtual_mem);
+        self
+    }
+
+    /// One record per part of `run`.
+    pub fn from_run(run: &DayRun) -> [PartRecord; 2] {
+        let parse_ns = run.parse.as_nanos() as u64;
+        let record = |part, (answer, time): &(Answer, std::time::Duration)| {
+            PartRecord::new(run.year, run.day, part, answer.clone(), parse_ns, time.as_nanos() as u64)
+        };
+        let mut records = [record(1, &run.part_1), record(2, &run.part_2)];
+        if let Some(memory) = &run.memory {
+            for (record, step) in records.iter_mut().zip([&memory.part_1, &memory.part_2]) {
+                *record = record.clone().with_memory(step).with_parse_memory(&memory.before_parse, &memory.after_parse);
+            }
+        }
+        records
+    }
+}
+
+impl Record for PartRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "year",
+        "day",
+        "part",
+        "answer",
+        "parse_ns",
+        "solve_ns",
+        "before_parse_physical_mem",
+        "before_parse_virtual_mem",
+        "after_parse_physical_mem",
+        "after_parse_virtual_mem",
+        "peak_physical_mem",
+        "peak_virtual_mem",
+        "heap_peak",
+        "allocations",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.answer.to_string(),
+            self.parse_ns.to_string(),
+            self.solve_ns.to_string(),
+            optional(self.before_parse_physical_mem),
+            optional(self.before_parse_virtual_mem),
+            optional(self.after_parse_physical_mem),
+            optional(self.after_parse_virtual_mem),
+            optional(self.peak_physical_mem),
+            optional(self.peak_virtual_mem),
+            optional(self.heap_peak),
+            optional(self.allocations),
+        ]
+    }
+}
+
+/// Outcome of checking one part against the answers file.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyRecord {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// One of `pass`, `fail`, `missing`, `recorded`, `no input` or `error`.
+    pub status: &'static str,
+    pub answer: Option<Answer>,
+    pub expected: Option<String>,
+}
+
+impl Record for VerifyRecord {
+    const COLUMNS: &'static [&'static str] = &["year", "day", "part", "status", "answer", "expected"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.status.to_string(),
+            optional(self.answer.as_ref()),
+            optional(self.expected.as_ref()),
+        ]
+    }
+}
+
+/// Statistics of one benchmarked step.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchRecord {
+    pub year: u32,
+    pub day: u32,
+    pub step: &'static str,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl BenchRecord {
+    /// One record per step of `bench`.
+    pub fn from_bench(year: u32, day: u32, bench: &Bench) -> Vec<BenchRecord> {
+        bench
+            .steps()
+            .into_iter()
+            .map(|(step, stats)| BenchRecord {
+                year,
+                day,
+                step,
+                runs: stats.runs,
+                min_ns: stats.min.as_nanos() as u64,
+                median_ns: stats.median.as_nanos() as u64,
+                mean_ns: stats.mean.as_nanos() as u64,
+                p95_ns: stats.p95.as_nanos() as u64,
+                stddev_ns: stats.stddev.as_nanos() as u64,
+            })
+            .collect()
+    }
+}
+
+impl Record for BenchRecord {
+    const COLUMNS: &'static [&'static str] =
+        &["year", "day", "step", "runs", "min_ns", "median_ns", "mean_ns", "p95_ns", "stddev_ns"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.step.to_string(),
+            self.runs.to_string(),
+            self.min_ns.to_string(),
+            self.median_ns.to_string(),
+            self.mean_ns.to_string(),
+            self.p95_ns.to_string(),
+            self.stddev_ns.to_string(),
+        ]
+    }
+}
+
+/// A registered solution.
+#[derive(Debug, Clone, Serialize)]
+pub struct SolutionRecord {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl Record for SolutionRecord {
+    const COLUMNS: &'static [&'static str] = &["year", "day"];
+
+    fn values(&self) -> Vec<String> {
+        vec![self.year.to_string(), self.day.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::memory::Snapshot;
+    use crate::report::{self, PartRecord, SolutionRecord};
+
+    #[test]
+    fn test_csv() {
+        let records = [
+            PartRecord::new(2024, 17, 1, Answer::from("4,6,3"), 10, 20),
+            PartRecord::new(2024, 17, 2, Answer::Str("say \"hi\"".to_string()), 10, 30),
+        ];
+        let csv = report::csv(&records);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].starts_with("year,day,part,answer,parse_ns,solve_ns,"));
+        assert_eq!(lines[1], "2024,17,1,\"4,6,3\",10,20,,,,,,,,");
+        assert_eq!(lines[2], "2024,17,2,\"say \"\"hi\"\"\",10,30,,,,,,,,");
+    }
+
+    #[test]
+    fn test_json() {
+        let records = [PartRecord::new(2023, 1, 1, Answer::Int(142), 5, 7)];
+        let json: serde_json::Value = serde_json::from_str(&report::json(&records)).unwrap();
+        assert_eq!(json[0]["answer"], 142);
+        assert_eq!(json[0]["solve_ns"], 7);
+        assert!(json[0]["heap_peak"].is_null());
+
+        let before = Snapshot { physical_mem: 1, virtual_mem: 2 };
+        let after = Snapshot { physical_mem: 3, virtual_mem: 4 };
+        let records = [PartRecord::new(2023, 1, 1, Answer::Int(142), 5, 7).with_parse_memory(&before, &after)];
+        let json: serde_json::Value = serde_json::from_str(&report::json(&records)).unwrap();
+        assert_eq!(json[0]["before_parse_virtual_mem"], 2);
+        assert_eq!(json[0]["after_parse_virtual_mem"], 4);
+
+        let json = report::json(&[SolutionRecord { year: 2025, day: 3 }]);
+        assert_eq!(json, "[\n  {\n    \"year\": 2025,\n    \"day\": 3\n  }\n]");
+    }
+}
@@ -0,0 +1,107 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+
+use crate::answer::Answer;
+use crate::memory::{self, MemoryReport, Snapshot};
+use crate::registry::Solution;
+
+/// Answers and timings of a full run of one day.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub year: u32,
+    pub day: u32,
+    pub parse: Duration,
+    pub part_1: (Answer, Duration),
+    pub part_2: (Answer, Duration),
+    pub memory: Option<MemoryReport>,
+}
+
+impl DayRun {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_1.1 + self.part_2.1
+    }
+}
+
+/// The error for a solution that panicked, carrying the panic message when it has one.
+pub(crate) fn panicked(payload: Box<dyn Any + Send>) -> anyhow::Error {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    match message {
+        Some(message) => anyhow!("Solution panicked: {message}"),
+        None => anyhow!("Solution panicked"),
+    }
+}
+
+/// Times `step`, also tracking its memory when `track_memory` is set.
+fn timed<T>(track_memory: bool, step: impl FnOnce() -> T) -> ((T, Duration), Option<memory::StepMemory>) {
+    let step = || {
+        let start = Instant::now();
+        let value = step();
+        (value, start.elapsed())
+    };
+    if track_memory {
+        let (value, memory) = memory::track(step);
+        (value, Some(memory))
+    } else {
+        (step(), None)
+    }
+}
+
+/// Parses `input` and runs both parts of `solution`, timing every step.
+///
+/// Parse errors are reported against `name`, and a panicking solution becomes an error
+/// so one broken day does not take the others down with it. Memory is measured for the
+/// whole process, so it is only meaningful when days run one at a time.
+pub fn run(solution: &Solution, name: &str, input: &str, track_memory: bool) -> Result<DayRun> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let before_parse = Snapshot::now();
+        let start = Instant::now();
+        let day = (solution.build)(input).map_err(|err| err.with_file(name))?;
+        let parse = start.elapsed();
+        let after_parse = Snapshot::now();
+
+        let (part_1, memory_1) = timed(track_memory, || day.part_1());
+        let (part_2, memory_2) = timed(track_memory, || day.part_2());
+
+        let memory = memory_1.zip(memory_2).map(|(part_1, part_2)| MemoryReport {
+            before_parse,
+            after_parse,
+            part_1,
+            part_2,
+        });
+        Ok(DayRun {
+            year: solution.year,
+            day: solution.day,
+            parse,
+            part_1,
+            part_2,
+            memory,
+        })
+    }))
+    .unwrap_or_else(|payload| Err(panicked(payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::registry;
+    use crate::runner;
+
+    #[test]
+    fn test_run() {
+        let solution = registry::find(2023, 1).unwrap();
+        let run = runner::run(solution, "sample", "1abc2\ntreb7uchet\n", true).unwrap();
+        assert_eq!((run.year, run.day), (2023, 1));
+        assert_eq!(run.part_1.0, Answer::Int(89));
+        assert!(run.total() >= run.parse);
+        assert!(run.memory.is_some());
+
+        let err = runner::run(solution, "sample", "abc\n", false).unwrap_err();
+        assert_eq!(err.to_string(), "Solution panicked: Line does not have a digit");
+    }
+}
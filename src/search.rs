@@ -0,0 +1,302 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest way found from the start to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// Every state along the way, from the start to the goal inclusive.
+    pub states: Vec<S>,
+}
+
+/// Every cheapest way from the start to the goals, as found by `dijkstra_all`.
+#[derive(Debug, Clone)]
+pub struct BestPaths<S, C> {
+    pub cost: C,
+    /// The goals reached at `cost`.
+    pub goals: Vec<S>,
+    /// For each state reached, the states it is entered from on one of its cheapest ways.
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Hash + Eq, C> BestPaths<S, C> {
+    /// Every state lying on at least one of the best paths.
+    pub fn states(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+        while let Some(state) = stack.pop() {
+            for prev in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(prev.clone()) {
+                    stack.push(prev.clone());
+                }
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search for the fewest steps from `start` to a state satisfying `is_goal`.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, Option<S>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(unwind(state, |s| parents[s].clone()));
+        }
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(state.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Fewest steps from `start` to every state it can reach.
+pub fn bfs_distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Cheapest path from `start` to a state satisfying `is_goal`. `successors` yields the
+/// states one move away along with the cost of the move, which must not be negative.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, guided by `heuristic`: a guess of the remaining cost that must never
+/// be more than the actual one.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let search = Search::run(start, successors, heuristic, is_goal, false)?;
+    let goal = search.goals[0];
+    let path = unwind(goal, |&i| search.parents[i].first().copied());
+    Some(Path {
+        cost: search.costs[goal],
+        states: path.states.into_iter().map(|i| search.states[i].clone()).collect(),
+    })
+}
+
+/// Like `dijkstra`, keeping every cheapest path to every goal reached at the lowest cost.
+pub fn dijkstra_all<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<BestPaths<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let search = Search::run(start, successors, |_| C::default(), is_goal, true)?;
+    let predecessors = search
+        .parents
+        .iter()
+        .enumerate()
+        .map(|(i, parents)| {
+            let parents = parents.iter().map(|&p| search.states[p].clone()).collect();
+            (search.states[i].clone(), parents)
+        })
+        .collect();
+    Some(BestPaths {
+        cost: search.costs[search.goals[0]],
+        goals: search.goals.iter().map(|&i| search.states[i].clone()).collect(),
+        predecessors,
+    })
+}
+
+/// Walks back from `goal` through `parent` to the start.
+fn unwind<S: Clone>(goal: S, mut parent: impl FnMut(&S) -> Option<S>) -> Path<S, usize> {
+    let mut states = vec![goal];
+    while let Some(prev) = parent(states.last().unwrap()) {
+        states.push(prev);
+    }
+    states.reverse();
+    Path { cost: states.len() - 1, states }
+}
+
+/// The states seen by a best-first search, numbered in the order they were found.
+struct Search<S, C> {
+    states: Vec<S>,
+    costs: Vec<C>,
+    parents: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S, C> Search<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Default,
+{
+    fn run<I>(
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool,
+        all: bool,
+    ) -> Option<Self>
+    where
+        I: IntoIterator<Item = (S, C)>,
+    {
+        let mut search = Search { states: vec![], costs: vec![], parents: vec![], goals: vec![] };
+        let mut index: HashMap<S, usize> = HashMap::new();
+        let mut closed = vec![false];
+        let mut heap = BinaryHeap::new();
+
+        heap.push(Reverse((heuristic(&start), C::default(), 0)));
+        index.insert(start.clone(), 0);
+        search.states.push(start);
+        search.costs.push(C::default());
+        search.parents.push(vec![]);
+
+        while let Some(Reverse((priority, cost, i))) = heap.pop() {
+            if closed[i] || cost > search.costs[i] {
+                continue;
+            }
+            if let Some(&goal) = search.goals.first() {
+                if priority > search.costs[goal] {
+                    break;
+                }
+            }
+            closed[i] = true;
+            if is_goal(&search.states[i]) {
+                search.goals.push(i);
+                if all {
+                    continue;
+                }
+                break;
+            }
+
+            for (next, step) in successors(&search.states[i]) {
+                let next_cost = cost + step;
+                let j = match index.entry(next) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        let j = search.states.len();
+                        search.states.push(entry.key().clone());
+                        search.costs.push(next_cost);
+                        search.parents.push(vec![i]);
+                        closed.push(false);
+                        entry.insert(j);
+                        heap.push(Reverse((next_cost + heuristic(&search.states[j]), next_cost, j)));
+                        continue;
+                    }
+                };
+                if next_cost < search.costs[j] {
+                    search.costs[j] = next_cost;
+                    search.parents[j] = vec![i];
+                    closed[j] = false;
+                    heap.push(Reverse((next_cost + heuristic(&search.states[j]), next_cost, j)));
+                } else if all && next_cost == search.costs[j] && !search.parents[j].contains(&i) {
+                    search.parents[j].push(i);
+                }
+            }
+        }
+
+        (!search.goals.is_empty()).then_some(search)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::Point;
+    use crate::grid::Grid;
+    use crate::search::{astar, bfs, bfs_distances, dijkstra, dijkstra_all};
+
+    fn maze() -> Grid<char> {
+        let input = "S...\n.##.\n...E\n.#..\n";
+        Grid::parse(input, input, ".#SE").unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let open = |p: &Point| grid.neighbours4(*p).filter(|&n| grid[n] != '#').collect::<Vec<_>>();
+        let path = bfs(Point::ORIGIN, open, |p| grid[*p] == 'E').unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!(path.states.first(), Some(&Point::ORIGIN));
+        assert_eq!(path.states.last(), Some(&Point::new(3, 2)));
+        assert!(path.states.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let distances = bfs_distances(Point::ORIGIN, open);
+        assert_eq!(distances.len(), 13);
+        assert_eq!(distances[&Point::new(0, 3)], 3);
+        assert_eq!(distances[&Point::new(3, 3)], 6);
+        assert_eq!(bfs(Point::ORIGIN, open, |p| grid[*p] == '#'), None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // Moving right costs 1 and moving down costs 10, with the goal at (3, 3).
+        let successors = |p: &Point| [(p.offset(1, 0), 1), (p.offset(0, 1), 10)].into_iter().filter(|(n, _)| n.x <= 3 && n.y <= 3);
+        let goal = |p: &Point| *p == Point::new(3, 3);
+        let path = dijkstra(Point::ORIGIN, successors, goal).unwrap();
+        assert_eq!(path.cost, 33);
+        assert_eq!(path.states.len(), 7);
+
+        let guided = astar(Point::ORIGIN, successors, |p| p.manhattan(Point::new(3, 3)), goal).unwrap();
+        assert_eq!(guided.cost, 33);
+
+        let best = dijkstra_all(Point::ORIGIN, successors, goal).unwrap();
+        assert_eq!(best.cost, 33);
+        assert_eq!(best.goals, vec![Point::new(3, 3)]);
+        // Every path costs the same, so every point is on a best one.
+        assert_eq!(best.states().len(), 16);
+    }
+
+    #[test]
+    fn test_all_best_paths() {
+        let grid = maze();
+        let open = |p: &Point| grid.neighbours4(*p).filter(|&n| grid[n] != '#').map(|n| (n, 1)).collect::<Vec<_>>();
+        let best = dijkstra_all(Point::ORIGIN, open, |p| grid[*p] == 'E').unwrap();
+        assert_eq!(best.cost, 5);
+        // Round either side of the wall, but never into the bottom row.
+        let states = best.states();
+        assert_eq!(states.len(), 10);
+        assert!(states.iter().all(|p| p.y < 3));
+    }
+}
@@ -1,4 +1,11 @@
+use crate::answer::Answer;
+
+/// A solved puzzle.
+///
+/// A day is parsed once from its input and the result is never modified afterwards. Each part
+/// solves from that parsed input alone, copying whatever it needs to mutate, so the parts can
+/// run in any order, any number of times, and give the same answers.
 pub trait Day {
-    fn part_1(&mut self) -> u64;
-    fn part_2(&mut self) -> u64;
-}
\ No newline at end of file
+    fn part_1(&self) -> Answer;
+    fn part_2(&self) -> Answer;
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use rayon::prelude::*;
 use lazy_static::lazy_static;
@@ -29,17 +30,20 @@ pub struct Day1 {
 
 impl Day1 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day1")
-            .expect("Cannot read file")
-            .split('\n')
+        parse::load("data/year2023/day1", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
             .map(String::from)
             .collect::<Vec<_>>();
 
         let digit_re = Regex::new(r"(one|two|three|four|five|six|seven|eight|nine)").expect("Invalid Regex");
-        Day1 {
+        Ok(Day1 {
             lines,
             digit_re
-        }
+        })
     }
 
     pub fn first_digit(line: &str) -> u32 {
@@ -54,13 +58,13 @@ impl Day1 {
 }
 
 impl Day for Day1 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.lines.par_iter().map(|line| {
             Day1::first_digit(line) * 10 + Day1::last_digit(line)
-        }).sum::<u32>() as u64
+        }).sum::<u32>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let res = self.lines.par_iter().map(|x| {
             let mut new_line = x.clone();
             for (key, val) in WORD_DIGIT_MAP.iter() {
@@ -90,20 +94,32 @@ impl Day for Day1 {
                 f_val * 10 + f_val
             }
         }).sum::<u32>();
-        res as u64
+        res.into()
     }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use crate::traits::Day;
     use crate::year2023::day1::Day1;
 
+    const SAMPLE: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
     #[test]
     fn test_regex() {
-        let day = Day1::new();
+        let day = Day1::parse(SAMPLE).unwrap();
         for s in day.digit_re.find_iter("somethingonetwothreeight") {
-            println!("{}", s.as_str());
+            eprintln!("{}", s.as_str());
         }
     }
+
+    #[test]
+    fn test_sample() {
+        let day = Day1::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 142u64.into());
+    }
 }
@@ -1,5 +1,6 @@
-use std::fs;
-use std::collections::HashSet;
+use crate::answer::Answer;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day11 {
@@ -10,40 +11,32 @@ pub struct Day11 {
 
 impl Day11 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day11")
-            .expect("Cannot read input file")
-            .split('\n')
-            .map(String::from)
-            .collect::<Vec<_>>();
+        parse::load("data/year2023/day11", Self::parse)
+    }
 
-        let mut points = Vec::new();
-        let mut used_x = HashSet::new();
-        let mut used_y = HashSet::new();
-        for (x, line) in lines.iter().enumerate() {
-            for (y, tile) in line.chars().enumerate() {
-                if tile == '#' {
-                    points.push((x, y));
-                    used_x.insert(x);
-                    used_y.insert(y);
-                }
-            }
-        }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, input, ".#")?;
 
-        let all_x = (0..lines.len()).collect::<HashSet<usize>>();
-        let all_y = (0..lines[0].len()).collect::<HashSet<usize>>();
-        let mut empty_x = all_x.difference(&used_x)
-            .cloned()
-            .collect::<Vec<_>>();
-        let mut empty_y = all_y.difference(&used_y)
-            .cloned()
-            .collect::<Vec<_>>();
-        empty_x.sort();
-        empty_y.sort();
-        Day11 {
+        let points = grid
+            .find_all(&'#')
+            .map(|pos| (pos.y as usize, pos.x as usize))
+            .collect();
+        let empty_x = grid
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&'#'))
+            .map(|(x, _)| x)
+            .collect();
+        let empty_y = grid
+            .columns()
+            .enumerate()
+            .filter_map(|(y, mut column)| column.all(|&tile| tile != '#').then_some(y))
+            .collect();
+        Ok(Day11 {
             points,
             empty_x,
             empty_y
-        }
+        })
     }
 
     fn get_distance_sum(arr: &Vec<(u64, u64)>) -> u64 {
@@ -58,7 +51,7 @@ impl Day11 {
 }
 
 impl Day for Day11 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut new_positions: Vec<(u64, u64)> = Vec::new();
         for pos in &self.points {
             let x = pos.0 as u64 + self.empty_x.binary_search(&pos.0).unwrap_or_else(|inc| inc) as u64;
@@ -66,16 +59,16 @@ impl Day for Day11 {
             new_positions.push((x, y));
         }
 
-        Day11::get_distance_sum(&new_positions)
+        Day11::get_distance_sum(&new_positions).into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut new_positions: Vec<(u64, u64)> = Vec::new();
         for pos in &self.points {
             let x = pos.0 as u64 + (self.empty_x.binary_search(&pos.0).unwrap_or_else(|inc| inc) as u64) * (1_000_000_u64 - 1);
             let y = pos.1 as u64 + (self.empty_y.binary_search(&pos.1).unwrap_or_else(|inc| inc) as u64) * (1_000_000_u64 - 1);
             new_positions.push((x, y));
         }
-        Day11::get_distance_sum(&new_positions)
+        Day11::get_distance_sum(&new_positions).into()
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use std::fs;
 use std::sync::{Arc, Mutex};
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use rayon::prelude::*;
 
@@ -10,21 +11,26 @@ pub struct Day12 {
 
 impl Day12 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day12")
-            .expect("Cannot read input file")
-            .split('\n')
-            .filter_map(|x| x.split_once(' '))
-            .map(|(line, nums_str)| {
-                let line = String::from(line);
+        parse::load("data/year2023/day12", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
+            .map(|x| {
+                let (line, nums_str) = parse::split_once(input, x, " ")?;
+                if let Some((i, c)) = line.char_indices().find(|&(_, c)| !"?.#".contains(c)) {
+                    return Err(ParseError::at(input, &line[i..i + c.len_utf8()], "one of `?.#`"));
+                }
                 let nums = nums_str
                     .split(',')
-                    .map(|x| x.parse::<usize>().expect("Cannot parse number"))
-                    .collect::<Vec<_>>();
-                (line, nums)
-            }).collect::<Vec<_>>();
-        Day12 {
+                    .map(|x| parse::number(input, x))
+                    .collect::<Result<Vec<usize>, _>>()?;
+                Ok((String::from(line), nums))
+            }).collect::<Result<Vec<_>, _>>()?;
+        Ok(Day12 {
             lines
-        }
+        })
     }
 
 
@@ -88,17 +94,17 @@ impl Day12 {
 }
 
 impl Day for Day12 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let cache = Arc::new(Mutex::new(HashMap::new()));
         let res: u64 = self.lines.par_iter()
             .map(|(line, nums)| {
                 let cache_clone = Arc::clone(&cache);
                 Day12::traverse_tree(line, nums, cache_clone)
             }).sum();
-        res
+        res.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let cache = Arc::new(Mutex::new(HashMap::new()));
         let res: u64 = self.lines.par_iter()
             .map(|(line, nums)| {
@@ -114,6 +120,6 @@ impl Day for Day12 {
                 let cache_clone = Arc::clone(&cache);
                 Day12::traverse_tree(&repeated_str, &repeated_num, cache_clone)
             }).sum();
-        res
+        res.into()
     }
 }
@@ -1,137 +1,72 @@
 use std::collections::HashMap;
-use std::fs;
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day14 {
-    lines: Vec<Vec<char>>,
+    lines: Grid<char>,
 }
 
 impl Day14 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day14")
-            .expect("Cannot read from file data/year2023/day14")
-            .split('\n')
-            .map(|x| x.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        Day14 {
-            lines
-        }
-    }
-
-    fn move_north(lines: &Vec<Vec<char>>)  -> Vec<Vec<char>> {
-        let mut rocks = lines.clone();
-        let mut av_x = vec![0; lines[0].len()];
-        for (x, row) in lines.iter().enumerate() {
-            for (y, &rock) in row.iter().enumerate() {
-                if rock == '.' {
-                    continue;
-                } else if rock == '#' {
-                    av_x[y] = x + 1;
-                } else {
-                    rocks[av_x[y]][y] = '0';
-                    if av_x[y] != x {
-                        rocks[x][y] = '.';
-                    }
-                    av_x[y] += 1;
-                }
-            }
-        }
-        rocks
+        parse::load("data/year2023/day14", Self::parse)
     }
 
-    fn move_south(lines: &Vec<Vec<char>>)  -> Vec<Vec<char>> {
-        let mut rocks = lines.clone();
-        let mut av_x = vec![lines.len() - 1; lines[0].len()];
-        for x in (0..lines.len()).rev() {
-            for y in (0..lines[x].len()).rev() {
-                let rock = lines[x][y];
-                if rock == '.' {
-                    continue;
-                } else if rock == '#' {
-                    av_x[y] = x - 1;
-                } else {
-                    rocks[av_x[y]][y] = '0';
-                    if av_x[y] != x {
-                        rocks[x][y] = '.';
-                    }
-                    av_x[y] -= 1;
-                }
-            }
-        }
-        rocks
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = Grid::parse(input, input, ".#O")?;
+        Ok(Day14 {
+            lines
+        })
     }
 
-    fn move_west(lines: &Vec<Vec<char>>)  -> Vec<Vec<char>> {
+    fn move_north(lines: &Grid<char>) -> Grid<char> {
         let mut rocks = lines.clone();
-        let mut av_y = vec![0; lines.len()];
-        for (x, row) in lines.iter().enumerate() {
-            for (y, &rock) in row.iter().enumerate() {
-                if rock == '.' {
-                    continue;
-                } else if rock == '#' {
-                    av_y[x] = y + 1;
-                } else {
-                    rocks[x][av_y[x]] = '0';
-                    if av_y[x] != y {
-                        rocks[x][y] = '.';
-                    }
-                    av_y[x] += 1;
+        let mut av_x = vec![0; lines.width()];
+        for (pos, &rock) in lines.iter() {
+            let (x, y) = (pos.y, pos.x as usize);
+            if rock == '.' {
+                continue;
+            } else if rock == '#' {
+                av_x[y] = x + 1;
+            } else {
+                rocks[Point::new(pos.x, av_x[y])] = '0';
+                if av_x[y] != x {
+                    rocks[pos] = '.';
                 }
+                av_x[y] += 1;
             }
         }
         rocks
     }
 
-    fn move_east(lines: &Vec<Vec<char>>)  -> Vec<Vec<char>> {
-        let mut rocks = lines.clone();
-        let mut av_y = vec![lines[0].len() - 1; lines.len()];
-        for x in (0..lines.len()).rev() {
-            for y in (0..lines[x].len()).rev() {
-                let rock = lines[x][y];
-                if rock == '.' {
-                    continue;
-                } else if rock == '#' {
-                    av_y[x] = y - 1;
-                } else {
-                    rocks[x][av_y[x]] = '0';
-                    if av_y[x] != y {
-                        rocks[x][y] = '.';
-                    }
-                    av_y[x] -= 1;
-                }
-            }
-        }
+    fn calc_sum(rocks: Grid<char>) -> u64 {
+        let n = rocks.height();
         rocks
+            .find_all(&'0')
+            .map(|pos| (n - pos.y as usize) as u64)
+            .sum()
     }
 
-    fn calc_sum(rocks: Vec<Vec<char>>) -> u64 {
-        let mut res = 0;
-        let n = rocks.len();
-        for (x, row) in rocks.iter().enumerate() {
-            for rock in row {
-                if *rock == '0' {
-                    res += n - x;
-                }
-            }
+    /// Tilts north, west, south and east in turn. Rotating clockwise after each tilt
+    /// brings the next direction to the north, and four rotations restore the grid.
+    fn one_cycle(start: &Grid<char>) -> Grid<char> {
+        let mut res = start.clone();
+        for _ in 0..4 {
+            res = Day14::move_north(&res).rotate_clockwise();
         }
-        res as u64
-    }
-
-    fn one_cycle(start: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-        let res = Day14::move_north(start);
-        let res = Day14::move_west(&res);
-        let res = Day14::move_south(&res);
-        Day14::move_east(&res)
+        res
     }
 }
 
 impl Day for Day14 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let north_rocks = Day14::move_north(&self.lines);
-        Day14::calc_sum(north_rocks)
+        Day14::calc_sum(north_rocks).into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut res = self.lines.clone();
         let mut cache = HashMap::new();
         let mut cycle_start = 0;
@@ -158,7 +93,7 @@ impl Day for Day14 {
         for _ in 0..nth {
             res = Day14::one_cycle(&res);
         }
-        Day14::calc_sum(res)
+        Day14::calc_sum(res).into()
     }
 }
 
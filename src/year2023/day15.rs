@@ -1,4 +1,5 @@
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 #[derive(Clone)]
@@ -16,20 +17,57 @@ impl PartialEq<Self> for Lens {
 impl Eq for Lens {}
 
 
+/// What a step does to the lenses labelled with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    /// `label-`: take the lens out of its box.
+    Remove,
+    /// `label=<focal length>`: put the lens in its box, or swap it for the one there.
+    Insert(i32),
+}
+
+/// One step of the initialization sequence, along with its text for hashing.
+struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
 pub struct Day15 {
-    lens_str: Vec<String>
+    steps: Vec<Step>
 }
 
 impl Day15 {
     pub fn new() -> Self {
-        let lens_str = fs::read_to_string("data/year2023/day15")
-            .expect("Cannot read input file")
+        parse::load("data/year2023/day15", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let steps = input
+            .trim_end()
             .split(',')
-            .filter_map(|x| if x.is_empty() { None } else { Some(String::from(x)) })
-            .collect::<Vec<_>>();
-        Day15 {
-            lens_str
+            .filter(|x| !x.is_empty())
+            .map(|x| Day15::parse_step(input, x))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Day15 {
+            steps
+        })
+    }
+
+    fn parse_step(input: &str, step: &str) -> Result<Step, ParseError> {
+        let (label, operation) = if let Some(label) = step.strip_suffix('-') {
+            (label, Operation::Remove)
+        } else {
+            let (label, power) = parse::split_once(input, step, "=")?;
+            if power.len() != 1 || !power.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ParseError::at(input, power, "a focal length digit"));
+            }
+            (label, Operation::Insert(parse::number(input, power)?))
+        };
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(ParseError::at(input, step, "a step like `rn=1` or `cm-`"));
         }
+        Ok(Step { text: String::from(step), label: String::from(label), operation })
     }
 
     fn hash_fn(input: &str) -> u8 {
@@ -46,35 +84,35 @@ impl Day15 {
 }
 
 impl Day for Day15 {
-    fn part_1(&mut self) -> u64 {
-        self.lens_str.iter()
-            .map(|x| Day15::hash_fn(x.as_str()) as u64)
+    fn part_1(&self) -> Answer {
+        self.steps.iter()
+            .map(|x| Day15::hash_fn(x.text.as_str()) as u64)
             .sum::<u64>()
+            .into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut boxes: Vec<Vec<Lens>> = vec![Vec::new(); 256];
-        self.lens_str
+        self.steps
             .iter()
-            .for_each(|x| {
-                if x.contains('-') {
-                    let label = x.split_once('-').unwrap().0;
-                    let h = Day15::hash_fn(label);
-                    let pair = Lens { label: String::from(label), power: 0 };
-                    if let Some(index) = boxes[h as usize].iter().position(|x| x == &pair) {
-                        boxes[h as usize].remove(index);
+            .for_each(|step| {
+                let label = step.label.as_str();
+                let h = Day15::hash_fn(label);
+                match step.operation {
+                    Operation::Remove => {
+                        let pair = Lens { label: String::from(label), power: 0 };
+                        if let Some(index) = boxes[h as usize].iter().position(|x| x == &pair) {
+                            boxes[h as usize].remove(index);
+                        }
                     }
-                } else if x.contains('=') {
-                    let (label, pwr) = x.split_once('=').unwrap();
-                    let h = Day15::hash_fn(label);
-                    let power = pwr.parse::<i32>()
-                        .unwrap_or_else(|err| panic!("Cannot parse power {pwr};;Err {err}"));
-                    if let Some(lens) = boxes[h as usize]
-                        .iter_mut()
-                        .find(|l| &(*l.label) == label) {
-                         lens.power = power;
-                    } else {
-                        boxes[h as usize].push(Lens { label: String::from(label), power });
+                    Operation::Insert(power) => {
+                        if let Some(lens) = boxes[h as usize]
+                            .iter_mut()
+                            .find(|l| &(*l.label) == label) {
+                             lens.power = power;
+                        } else {
+                            boxes[h as usize].push(Lens { label: String::from(label), power });
+                        }
                     }
                 }
             });
@@ -86,7 +124,31 @@ impl Day for Day15 {
                     .map(|(y, lens)| {
                         (i as i32 + 1) * (y as i32 + 1) * lens.power
                     }).sum::<i32>()
-            ).sum::<i32>() as u64
+            ).sum::<i32>()
+            .into()
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2023::day15::Day15;
+
+    const SAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+    #[test]
+    fn test_sample() {
+        let day = Day15::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 1320u64.into());
+        assert_eq!(day.part_2(), 145i32.into());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day15::parse("rn=1,qp=x\n").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (9, "x"));
+        assert!(Day15::parse("rn=1,qp\n").is_err());
+        assert!(Day15::parse("=1\n").is_err());
+    }
+}
@@ -1,181 +1,67 @@
 use std::collections::HashSet;
-use std::fs;
 use rayon::prelude::*;
 
+use crate::answer::Answer;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
-#[derive(Debug, Hash, Clone, Eq, PartialEq)]
-enum Direction {
-    South,
-    North,
-    East,
-    West
-}
-
-impl Direction {
-    fn is_horizontal(&self) -> bool {
-        matches!(self, Direction::East | Direction::West)
-    }
-}
-
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
-struct Point {
-    x: i32,
-    y: i32
-}
-
-#[derive(Hash, Clone, Eq, PartialEq)]
+#[derive(Hash, Clone, Copy, Eq, PartialEq)]
 struct Beam {
     direction: Direction,
     point: Point
 }
 
-impl Point {
-    fn is_out_of_bound(&self, bound: &Point) -> bool {
-        self.x < 0 || self. y < 0 || self.x >= bound.x || self.y >= bound.y
-    }
-}
-
-
 pub struct Day16 {
-    lines: Vec<Vec<char>>,
-    bound: Point
+    grid: Grid<char>,
 }
 
 impl Day16 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day16")
-            .expect("Cannot read file")
-            .split('\n')
-            .map(|line| line.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let bound = Point { x: lines.len() as i32, y: lines[0].len() as i32 };
-        Day16{
-            lines,
-            bound
-        }
+        parse::load("data/year2023/day16", Self::parse)
     }
 
-    fn traverse(&self, mut beam: Beam, visited: &mut HashSet<Beam>) {
-        while !visited.contains(&beam) && !beam.point.is_out_of_bound(&self.bound) {
-            visited.insert(beam.clone());
-            let tile = self.lines[beam.point.x as usize][beam.point.y as usize];
-
-            if (tile == '|' && !beam.direction.is_horizontal()) ||
-                (tile == '-' && beam.direction.is_horizontal()) ||
-                tile == '.' {
-                match beam.direction {
-                    Direction::South => {
-                        let next_point =  Point { x: beam.point.x+1, y: beam.point.y };
-                        beam.point = next_point;
-                    }
-                    Direction::North => {
-                        let next_point =  Point { x: beam.point.x-1, y: beam.point.y };
-                        beam.point = next_point;
-                    }
-                    Direction::East => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y+1 };
-                        beam.point = next_point;
-                    }
-                    Direction::West => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y-1 };
-                        beam.point = next_point;
-                    }
-                }
-                continue;
-            }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, input, ".|-/\\")?;
+        Ok(Day16{
+            grid
+        })
+    }
 
-            if tile == '/' {
-                match beam.direction {
-                    Direction::South => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y-1 };
-                        beam.point = next_point;
-                        beam.direction = Direction::West;
-                    }
-                    Direction::North => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y+1 };
-                        beam.point = next_point;
-                        beam.direction = Direction::East;
-                    }
-                    Direction::East => {
-                        let next_point =  Point { x: beam.point.x-1, y: beam.point.y };
-                        beam.point = next_point;
-                        beam.direction = Direction::North;
-                    }
-                    Direction::West => {
-                        let next_point =  Point { x: beam.point.x+1, y: beam.point.y };
-                        beam.point = next_point;
-                        beam.direction = Direction::South;
-                    }
-                }
-                continue;
-            }
+    fn tile(&self, point: Point) -> Option<char> {
+        self.grid.get(point).copied()
+    }
 
-            if tile == '\\' {
-                match beam.direction {
-                    Direction::South => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y+1 };
-                        beam.point = next_point;
-                        beam.direction = Direction::East;
-                    }
-                    Direction::North => {
-                        let next_point =  Point { x: beam.point.x, y: beam.point.y-1 };
-                        beam.point = next_point;
-                        beam.direction = Direction::West;
-                    }
-                    Direction::East => {
-                        let next_point =  Point { x: beam.point.x+1, y: beam.point.y };
-                        beam.point = next_point;
-                        beam.direction = Direction::South;
-                    }
-                    Direction::West => {
-                        let next_point =  Point { x: beam.point.x-1, y: beam.point.y };
-                        beam.point = next_point;
-                        beam.direction = Direction::North;
-                    }
+    fn traverse(&self, mut beam: Beam, visited: &mut HashSet<Beam>) {
+        while !visited.contains(&beam) {
+            let Some(tile) = self.tile(beam.point) else {
+                break;
+            };
+            visited.insert(beam);
+
+            match tile {
+                '/' if beam.direction.is_horizontal() => beam.direction = beam.direction.turn_left(),
+                '/' => beam.direction = beam.direction.turn_right(),
+                '\\' if beam.direction.is_horizontal() => beam.direction = beam.direction.turn_right(),
+                '\\' => beam.direction = beam.direction.turn_left(),
+                '|' if beam.direction.is_horizontal() => {
+                    self.split(beam.point, Direction::North, Direction::South, visited);
+                    return;
                 }
-                continue;
-            }
-
-            if tile == '|' {
-                let beam_1 = Beam {
-                    direction: Direction::North,
-                    point: Point {
-                        x: beam.point.x - 1,
-                        y: beam.point.y
-                    }
-                };
-                let beam_2 = Beam {
-                    direction: Direction::South,
-                    point: Point {
-                        x: beam.point.x + 1,
-                        y: beam.point.y
-                    }
-                };
-                self.traverse(beam_1, visited);
-                self.traverse(beam_2, visited);
-                return;
+                '-' if beam.direction.is_vertical() => {
+                    self.split(beam.point, Direction::East, Direction::West, visited);
+                    return;
+                }
+                _ => {}
             }
+            beam.point = beam.point.step(beam.direction);
+        }
+    }
 
-            if tile == '-' {
-                let beam_1 = Beam {
-                    direction: Direction::East,
-                    point: Point {
-                        x: beam.point.x,
-                        y: beam.point.y + 1
-                    }
-                };
-                let beam_2 = Beam {
-                    direction: Direction::West,
-                    point: Point {
-                        x: beam.point.x,
-                        y: beam.point.y - 1
-                    }
-                };
-                self.traverse(beam_1, visited);
-                self.traverse(beam_2, visited);
-                return;
-            }
+    fn split(&self, point: Point, dir1: Direction, dir2: Direction, visited: &mut HashSet<Beam>) {
+        for direction in [dir1, dir2] {
+            self.traverse(Beam { direction, point: point.step(direction) }, visited);
         }
     }
 
@@ -184,47 +70,48 @@ impl Day16 {
         self.traverse(beam, &mut visited);
         visited
             .iter()
-            .map(|beam| beam.point.clone())
+            .map(|beam| beam.point)
             .collect::<HashSet<_>>()
             .len() as u64
     }
 }
 
 impl Day for Day16 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.start_travel(Beam {
             direction: Direction::East,
-            point: Point { x: 0, y: 0 }
-        })
+            point: Point::ORIGIN
+        }).into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
+        let (height, width) = (self.grid.height() as i32, self.grid.width() as i32);
         let mut beams: Vec<Beam> = vec![];
-        for i in 0..self.bound.y {
+        for i in 0..width {
             beams.push(Beam {
                 direction: Direction::South,
-                point: Point{ x: 0, y: i }
+                point: Point::new(i, 0)
             });
         }
 
-        for i in 0..self.bound.x {
+        for i in 0..height {
             beams.push(Beam {
                 direction: Direction::East,
-                point: Point{ x: i, y: 0 }
+                point: Point::new(0, i)
             });
         }
 
-        for i in 0..self.bound.x {
+        for i in 0..height {
             beams.push(Beam {
                 direction: Direction::West,
-                point: Point{ x: i, y: self.bound.y - 1 }
+                point: Point::new(width - 1, i)
             });
         }
 
-        for i in 0..self.bound.y {
+        for i in 0..width {
             beams.push(Beam {
                 direction: Direction::North,
-                point: Point{ x: self.bound.x - 1, y: i }
+                point: Point::new(i, height - 1)
             });
         }
 
@@ -234,6 +121,7 @@ impl Day for Day16 {
             .map(|x| self.start_travel(x))
             .max()
             .expect("No beams found")
+            .into()
     }
 }
 
@@ -1,189 +1,109 @@
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use crate::answer::Answer;
+use crate::geom::Direction;
+use crate::parse::{self, ParseError};
+use crate::polygon::Polygon;
 use crate::traits::Day;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y}
-    }
-
-    fn left(&self) -> Point {
-        Point::new(self.x, self.y-1)
-    }
-    fn right(&self) -> Point {
-        Point::new(self.x, self.y+1)
-    }
-
-    fn up(&self) -> Point {
-        Point::new(self.x-1, self.y)
-    }
-
-    fn down(&self) -> Point {
-        Point::new(self.x+1, self.y)
-    }
-}
-
 pub struct Day18 {
-    lines: Vec<(char, u8, String)>
+    /// Each dig plan line, with the move its color hides.
+    lines: Vec<(Direction, u8, (Direction, i64))>
 }
 
 impl Day18 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day18")
-            .expect("Cannot read file")
-            .split('\n')
+        parse::load("data/year2023/day18", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
             .map(|line| {
                 let mut parts = line
                     .split_whitespace();
-                let dir = parts.next()
-                    .expect("Direction not provided")
-                    .as_bytes()[0] as char;
-                let length = parts.next()
-                    .expect("Length now provided")
-                    .parse::<u8>()
-                    .expect("Cannot parse length, invalid character");
-                let color = parts.next()
-                    .expect("Color not provided");
-                if color.chars().count() <= 3 {
-                    panic!("Empty color provided {color}");
-                }
-                let color = String::from(&color[2..color.len() - 1]);
-                (dir, length, color)
-            }).collect::<Vec<_>>();
+                let dir = parse::expect(input, parts.next(), line, "a direction")?;
+                let dir = match dir {
+                    "U" | "D" | "L" | "R" => Direction::from_char(dir.as_bytes()[0] as char),
+                    _ => None,
+                }.ok_or_else(|| ParseError::at(input, dir, "one of `UDLR`"))?;
+                let length = parse::expect(input, parts.next(), line, "a length")?;
+                let length = parse::number::<u8>(input, length)?;
+                let color = parse::expect(input, parts.next(), line, "a color")?;
+                let hex = color.strip_prefix("(#")
+                    .and_then(|c| c.strip_suffix(')'))
+                    .filter(|c| c.len() == 6 && c.chars().all(|x| x.is_ascii_hexdigit()))
+                    .ok_or_else(|| ParseError::at(input, color, "a color like `(#70c710)`"))?;
+                let hidden = Day18::decode_color(hex)
+                    .ok_or_else(|| ParseError::at(input, color, "a direction digit 0-3"))?;
+                Ok((dir, length, hidden))
+            }).collect::<Result<Vec<_>, ParseError>>()?;
 
-        Day18 {
+        Ok(Day18 {
             lines
-        }
+        })
     }
 
-    fn get_all_vertex_and_vertical_edge_start(&self) -> (HashSet<Point>, HashSet<Point>, Point, Point) {
-        let mut vertex = HashSet::new();
-        let mut vertical_start = HashSet::new();
-        let mut smallest = Point::new(0, 0);
-        let mut largest = Point::new(0, 0);
+    /// Cubic metres held by the lagoon dug out along `moves`, trench included.
+    fn lagoon(moves: impl IntoIterator<Item = (Direction, i64)>) -> u64 {
+        let polygon = Polygon::from_moves((0, 0), moves);
+        polygon.interior_points() + polygon.boundary_len()
+    }
 
-        let mut pos = Point::new(0, 0);
-        vertex.insert(pos);
+    /// The move hidden in six hex digits of color: five of distance, then `0123` for `RDLU`.
+    /// `None` when the last digit is not a direction.
+    fn decode_color(hex: &str) -> Option<(Direction, i64)> {
+        let (distance, direction) = hex.split_at(5);
+        let distance = i64::from_str_radix(distance, 16).ok()?;
+        let direction = match direction {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            _ => return None,
+        };
+        Some((direction, distance))
+    }
+}
 
-        for (dir, length, _) in &self.lines {
-            if *dir == 'L' {
-                for _ in 0..*length {
-                    pos = pos.left();
-                    vertex.insert(pos);
-                }
-                if pos.y < smallest.y {
-                    smallest.y = pos.y;
-                }
-            } else if *dir == 'R' {
-                for _ in 0..*length {
-                    pos = pos.right();
-                    vertex.insert(pos);
-                }
-                if pos.y > largest.y {
-                    largest.y = pos.y;
-                }
-            } else if *dir == 'U' {
-                for _ in 0..*length {
-                    pos = pos.up();
-                    vertical_start.insert(pos);
-                    vertex.insert(pos);
-                }
-                if pos.x < smallest.x {
-                    smallest.x = pos.x;
-                }
-            } else if *dir == 'D' {
-                vertical_start.insert(pos);
-                for _ in 0..length-1 {
-                    pos = pos.down();
-                    vertical_start.insert(pos);
-                    vertex.insert(pos);
-                }
-                pos = pos.down();
-                vertex.insert(pos);
-                if pos.x > largest.x {
-                    largest.x = pos.x;
-                }
-            }
-        }
-        (vertex, vertical_start, smallest, largest)
+impl Day for Day18 {
+    fn part_1(&self) -> Answer {
+        Day18::lagoon(self.lines.iter().map(|&(dir, length, _)| (dir, length as i64))).into()
     }
 
-    fn check_vertex_inside_box(point: &Point, vertical_vertex: &HashSet<Point>, y_lim: i32) -> bool {
-        let mut current= *point;
-        let mut count = 0;
-        for _ in point.y+1..y_lim {
-            current = current.right();
-            if vertical_vertex.contains(&current) {
-                count += 1;
-            }
-        }
-        count % 2 == 1
+    fn part_2(&self) -> Answer {
+        Day18::lagoon(self.lines.iter().map(|&(_, _, hidden)| hidden)).into()
     }
 }
 
-impl Day for Day18 {
-    fn part_1(&mut self) -> u64 {
-        let (vertex, vertical_vertex, smallest, largest) = self.get_all_vertex_and_vertical_edge_start();
-        let mut count = 0;
-        println!("Smallest: {smallest:?} ;; Largest: {largest:?}");
-        for x in smallest.x..=largest.x {
-            for y in smallest.y..=largest.y {
-                let p = Point::new(x, y);
-                if vertex.contains(&p) || Day18::check_vertex_inside_box(&p, &vertical_vertex, largest.y+1) {
-                    count += 1;
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2023::day18::Day18;
+
+    const SAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
 
-        count
+    #[test]
+    fn test_sample() {
+        let day = Day18::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 62u64.into());
+        assert_eq!(day.part_2(), 952408144115u64.into());
     }
 
-    fn part_2(&mut self) -> u64 {
-        let mut current = Point::new(0, 0);
-        let mut vertical_edges: HashMap<i32, Vec<i32>> = HashMap::new();
-        let mut left_edge_sum = 0_u64;
-        for (_, _, hex) in &self.lines {
-            let num_str = &hex.as_str()[0..hex.len()-1];
-            let num = i32::from_str_radix(num_str, 16)
-                .unwrap_or_else(|err| {
-                    panic!("Invalid hex string found {num_str};; Err {err}");
-                });
-            let dir = hex.as_bytes()[hex.len()-1] as char;
-            if dir == '0' {
-                current.y += num;
-            } else if dir == '2' {
-                current.y -= num;
-                left_edge_sum += num as u64;
-            } else if dir == '1' {
-                //     down
-                for i in current.x..current.x+num {
-                    vertical_edges.entry(i).or_default().push(current.y);
-                }
-                current.x += num;
-            } else if dir == '3' {
-                //     up
-                for i in current.x-num..current.x {
-                    vertical_edges.entry(i).or_default().push(current.y);
-                }
-                current.x -= num;
-            }
-        }
-        let mut sum = 0_u64;
-        for (_, y) in vertical_edges.iter_mut() {
-            y.sort();
-            for chunk in y.chunks(2) {
-                if chunk.len() != 2 {
-                    panic!("Uneven pairs of coordinates");
-                }
-                sum += (chunk[1] - chunk[0] + 1) as u64;
-            }
-        }
-        sum + left_edge_sum + 1
+    #[test]
+    fn test_bad_direction_digit() {
+        assert!(Day18::parse("R 6 (#70c714)\n").is_err());
     }
 }
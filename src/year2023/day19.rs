@@ -1,5 +1,7 @@
 use std::collections::HashMap;
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::ranges::{InclusiveInterval, Interval};
 use crate::traits::Day;
 
 pub struct Day19 {
@@ -9,31 +11,45 @@ pub struct Day19 {
 
 impl Day19 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2023/day19")
-            .expect("Cannot read input file");
-        let (workflow_str, parts_str) = data
-            .split_once("\n\n")
-            .expect("Input not present");
+        parse::load("data/year2023/day19", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let (workflow_str, parts_str) = parse::split_once(input, input, "\n\n")?;
 
-        let workflows: HashMap<String, Vec<String>> = workflow_str.split('\n')
+        let workflows: HashMap<String, Vec<String>> = workflow_str.lines()
             .map(|line| {
-                let (key, mut vec_str) = line.split_once('{').expect("Line malformed");
-                vec_str = &vec_str[..vec_str.len() - 1];
-                (String::from(key), vec_str.split(',').map(String::from).collect::<Vec<_>>())
-            }).collect();
+                let (key, vec_str) = parse::split_once(input, line, "{")?;
+                let vec_str = parse::expect(input, vec_str.strip_suffix('}'), line, "a workflow ending with `}`")?;
+                let rules = vec_str.split(',').collect::<Vec<_>>();
+                for rule in &rules[..rules.len() - 1] {
+                    let (cond, _) = parse::split_once(input, rule, ":")?;
+                    let mut chars = cond.chars();
+                    if !chars.next().is_some_and(|c| "xmas".contains(c)) || !chars.next().is_some_and(|c| "<>".contains(c)) {
+                        return Err(ParseError::at(input, cond, "a condition like `a<2006`"));
+                    }
+                    parse::number::<u32>(input, &cond[2..])?;
+                }
+                Ok((String::from(key), rules.into_iter().map(String::from).collect::<Vec<_>>()))
+            }).collect::<Result<_, ParseError>>()?;
 
-        let parts = parts_str.split('\n')
-            .map(|mut line| {
-                line = &line[1..line.len() - 1];
-                line.split(',').map(|kv| {
-                    let (k, v) = kv.split_once('=').expect("Mapping incorrect");
-                    (k.as_bytes()[0] as char, v.parse::<u32>().expect("Parts value in correct"))
+        let parts = parts_str.lines()
+            .map(|line| {
+                let ratings = line.strip_prefix('{')
+                    .and_then(|l| l.strip_suffix('}'))
+                    .ok_or_else(|| ParseError::at(input, line, "a part like `{x=787,m=2655,a=1222,s=2876}`"))?;
+                ratings.split(',').map(|kv| {
+                    let (k, v) = parse::split_once(input, kv, "=")?;
+                    if k.len() != 1 || !"xmas".contains(k) {
+                        return Err(ParseError::at(input, k, "one of `xmas`"));
+                    }
+                    Ok((k.as_bytes()[0] as char, parse::number::<u32>(input, v)?))
                 }).collect()
-            }).collect::<Vec<_>>();
-        Day19 {
+            }).collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Day19 {
             workflows,
             parts,
-        }
+        })
     }
 
     fn is_part_accepted(&self, part: &HashMap<char, u32>) -> bool {
@@ -81,28 +97,25 @@ impl Day19 {
         }
     }
 
-    fn get_range_combination(parts: &HashMap<char, [u32; 2]>) -> u64 {
+    fn get_range_combination(parts: &HashMap<char, Interval>) -> u64 {
         parts.values()
-            .map(|res| res[1] as u64 - res[0] as u64 + 1)
+            .map(Interval::len)
             .product()
     }
 }
 
 impl Day for Day19 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.parts.iter()
             .filter_map(|part| if self.is_part_accepted(part) {
                 Some(part.values().sum::<u32>() as u64)
-            } else { None }).sum()
+            } else { None }).sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let part_ranges: HashMap<char, [u32; 2]> = vec![
-            ('x', [1, 4000]),
-            ('m', [1, 4000]),
-            ('a', [1, 4000]),
-            ('s', [1, 4000])
-        ].into_iter().collect();
+    fn part_2(&self) -> Answer {
+        let part_ranges: HashMap<char, Interval> = "xmas".chars()
+            .map(|c| (c, InclusiveInterval::new(1, 4000).into()))
+            .collect();
 
         let mut stack = Vec::new();
         stack.push((part_ranges, "in"));
@@ -127,18 +140,16 @@ impl Day for Day19 {
                 if let Some((cond, nxt)) = condition.split_once(':') {
                     let a = cond.as_bytes()[0] as char;
                     let op = cond.as_bytes()[1] as char;
-                    let val = cond[2..].parse::<u32>().expect("Condition value not correct");
-                    let vals = ranges.get(&a).unwrap();
-                    let low = vals[0];
-                    let high = vals[1];
-                    let mut new_ranges = ranges.clone();
-                    if op == '>' {
-                        new_ranges.insert(a, [val+1, high]);
-                        ranges.insert(a, [low, val]);
+                    let val = cond[2..].parse::<i64>().expect("Condition value not correct");
+                    let (matched, rest) = if op == '>' {
+                        let (below, above) = ranges[&a].split_at(val + 1);
+                        (above, below)
                     } else {
-                        new_ranges.insert(a, [low, val-1]);
-                        ranges.insert(a, [val, high]);
-                    }
+                        ranges[&a].split_at(val)
+                    };
+                    let mut new_ranges = ranges.clone();
+                    new_ranges.insert(a, matched);
+                    ranges.insert(a, rest);
                     stack.push((new_ranges, nxt));
                 } else {
                     stack.push((ranges, condition));
@@ -147,7 +158,7 @@ impl Day for Day19 {
             }
         }
 
-        ttl
+        ttl.into()
     }
 }
 
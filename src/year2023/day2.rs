@@ -1,90 +1,113 @@
-use std::collections::HashMap;
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use lazy_static::lazy_static;
 use rayon::prelude::*;
 
-lazy_static! {
-    static ref LIMITS: HashMap<String, i32> = {
-        let mut limit: HashMap<String, i32> = HashMap::new();
-        limit.insert(String::from("red"), 12);
-        limit.insert(String::from("green"), 13);
-        limit.insert(String::from("blue"), 14);
-        limit
-    };
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
 }
 
+impl Color {
+    /// The most cubes of this color the bag holds in part 1.
+    fn limit(self) -> u32 {
+        match self {
+            Color::Red => 12,
+            Color::Green => 13,
+            Color::Blue => 14,
+        }
+    }
+}
+
+/// The handfuls of cubes shown in one game, each a list of `(count, color)` draws.
+type Game = Vec<Vec<(u32, Color)>>;
+
 pub struct Day2 {
-    lines: Vec<String>,
+    games: Vec<Game>,
 }
 
 impl Day2 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day2")
-            .unwrap_or_else(|er| panic!("Error reading the file. Err {er}"))
-            .split('\n')
-            .map(String::from)
-            .collect::<Vec<_>>();
-        Day2 {
-            lines
-        }
+        parse::load("data/year2023/day2", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let games = input
+            .lines()
+            .map(|line| {
+                let (_, rounds) = parse::split_once(input, line, ":")?;
+                rounds.split(';').map(|round| {
+                    round.split(',').map(|draw| Day2::parse_draw(input, draw.trim())).collect()
+                }).collect()
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Day2 {
+            games
+        })
+    }
+
+    fn parse_draw(input: &str, draw: &str) -> Result<(u32, Color), ParseError> {
+        let (count, color) = parse::split_once(input, draw, " ")?;
+        let color = match color {
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "blue" => Color::Blue,
+            _ => return Err(ParseError::at(input, color, "one of `red`, `green` or `blue`")),
+        };
+        Ok((parse::number(input, count)?, color))
+    }
+
+    /// The fewest cubes of `color` the bag could have held for `game`.
+    fn fewest(game: &Game, color: Color) -> u32 {
+        game.iter().flatten().filter(|&&(_, c)| c == color).map(|&(count, _)| count).max().unwrap_or(0)
     }
 }
 
 impl Day for Day2 {
-    fn part_1(&mut self) -> u64 {
-        self.lines.par_iter().enumerate().filter_map(|(id, line)| {
-            let mut parts = line.splitn(2, ':').skip(1);
-            let is_game_possible = parts.next()
-                .expect("Games data not present")
-                .split(';')
-                .map(|x| x.trim())
-                .all(|game|
-                    game.split(',')
-                        .all(|game| {
-                            let mut game_part = game.trim().splitn(2, ' ');
-                            let count = game_part.next()
-                                .expect("Game count part not present")
-                                .parse::<i32>()
-                                .expect("Number malformed");
-                            let ball = game_part.next()
-                                .expect("Game ball part not present")
-                                .to_string();
-                            let limit = *LIMITS.get(&ball).unwrap_or_else(|| panic!("Unrecognized color {ball}"));
-                            count <= limit
-                        })
-                );
+    fn part_1(&self) -> Answer {
+        self.games.par_iter().enumerate().filter_map(|(id, game)| {
+            let is_game_possible = game.iter().flatten().all(|&(count, color)| count <= color.limit());
             if is_game_possible {
                 Some(id as u64 + 1)
             } else {
                 None
             }
-        }).sum::<u64>()
+        }).sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        self.lines.par_iter().map(|line| {
-            let game_line = line.split_once(':').map(|x| x.1).expect("Game line not present");
-            let mut min_red = 0;
-            let mut min_blue = 0;
-            let mut min_green = 0;
-            for games in game_line.trim().split(';') {
-                for balls in games.trim().split(',') {
-                    let (count, ball) = balls.trim().split_once(' ').expect("Count and ball not present");
-                    let count = count.parse::<i32>().expect("Count malformed");
-                    if ball == "red" && count > min_red {
-                        min_red  = count;
-                    }
-                    if ball == "green" && count > min_green {
-                        min_green  = count;
-                    }
-                    if ball == "blue" && count > min_blue {
-                        min_blue  = count;
-                    }
-                }
-            }
-            min_red * min_blue * min_green
-        }).sum::<i32>() as u64
+    fn part_2(&self) -> Answer {
+        self.games.par_iter().map(|game| {
+            [Color::Red, Color::Green, Color::Blue].iter().map(|&color| Day2::fewest(game, color) as u64).product::<u64>()
+        }).sum::<u64>().into()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2023::day2::Day2;
+
+    const SAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day2::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 8u64.into());
+        assert_eq!(day.part_2(), 2286u64.into());
     }
 
+    #[test]
+    fn test_parse_error() {
+        let err = Day2::parse("Game 1: 3 blue, 4 purple\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 19, "purple"));
+        assert!(Day2::parse("Game 1: x blue\n").is_err());
+    }
 }
@@ -1,161 +1,351 @@
 use std::collections::{HashMap, VecDeque};
-use std::fs;
+use std::fmt::Write;
+use crate::answer::Answer;
+use crate::graph::Interner;
+use crate::math::lcm_all;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use crate::year2023::day8::Day8;
 
-pub struct Day20 {
-    mapping: HashMap<String, Vec<String>>,
-    states: HashMap<String, State>
+/// How long to wait for the inputs of the module before `rx` to settle into cycles.
+const MAX_PRESSES: u64 = 100_000;
+
+/// Index of a module in its `Network`.
+pub type ModuleId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Module {
+    /// The button, sending a low pulse to the broadcaster on each press.
+    Button,
+    Broadcaster,
+    /// `%`: flips on each low pulse and sends its new state, ignoring high pulses.
+    FlipFlop,
+    /// `&`: remembers the last pulse from each input and sends low only when all were high.
+    Conjunction,
+    /// A module only ever sent to, like `rx`.
+    Sink,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pulse {
+    pub from: ModuleId,
+    pub to: ModuleId,
+    pub high: bool,
+}
 
-#[derive(Debug)]
+/// Everything the modules remember between presses. Hashable, so a repeated state shows
+/// the network has gone round a cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct State {
-    ff: bool,
-    con: Option<HashMap<String, bool>>
+    on: Vec<bool>,
+    /// For each module, the last pulse from each of its inputs, in the order of `inputs`.
+    memory: Vec<Vec<bool>>,
 }
 
-impl Day20 {
-    pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day20")
-            .expect("Cannot read file")
-            .split('\n')
-            .map(String::from)
-            .collect::<Vec<_>>();
-
-        let mut mapping: HashMap<String, Vec<String>> = HashMap::new();
-        let mut states: HashMap<String, State> = HashMap::new();
-        for line in lines {
-            let (mut key, val) = line.split_once(" -> ").expect("Line malformed");
-            let vals = val.split(", ");
-            let first_char = key.as_bytes()[0] as char;
-            if first_char == '%' {
-                key = &key[1..];
-                let state = State {
-                    ff: false,
-                    con: None
-                };
-                states.insert(String::from(key), state);
-            } else if first_char == '&' {
-                key = &key[1..];
-                let state = State {
-                    ff: false,
-                    con: Some(HashMap::new())
-                };
-                states.insert(String::from(key), state);
-            }
+/// The modules and their wiring, with names swapped for ids.
+#[derive(Debug, Clone)]
+pub struct Network {
+    names: Interner,
+    modules: Vec<Module>,
+    outputs: Vec<Vec<ModuleId>>,
+    inputs: Vec<Vec<ModuleId>>,
+    button: ModuleId,
+}
 
-            mapping.insert(String::from(key), vals.map(String::from).collect::<Vec<_>>());
+impl Network {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut names = Interner::new();
+        let button = names.intern("button");
+        let mut modules = vec![Module::Button];
+        let mut outputs = vec![vec![]];
+        for line in input.lines() {
+            let (key, val) = parse::split_once(input, line, " -> ")?;
+            let (module, name) = match key.as_bytes().first() {
+                Some(b'%') => (Module::FlipFlop, &key[1..]),
+                Some(b'&') => (Module::Conjunction, &key[1..]),
+                _ if key == "broadcaster" => (Module::Broadcaster, key),
+                _ => return Err(ParseError::at(input, key, "`broadcaster` or a name after `%` or `&`")),
+            };
+            if name.is_empty() {
+                return Err(ParseError::at(input, line, "a module name"));
+            }
+            let id = names.intern(name);
+            let targets: Vec<ModuleId> = val.split(", ").map(|target| names.intern(target)).collect();
+            modules.resize(names.len(), Module::Sink);
+            outputs.resize(names.len(), vec![]);
+            modules[id] = module;
+            outputs[id] = targets;
         }
+        let broadcaster = names
+            .get("broadcaster")
+            .filter(|&id| modules[id] == Module::Broadcaster)
+            .ok_or_else(|| ParseError::at(input, &input[input.len()..], "a `broadcaster -> ...` line"))?;
+        outputs[button] = vec![broadcaster];
 
-        states.iter_mut().for_each(|(k, v)| {
-            if let Some(km) = &mut v.con {
-                for (kmap, vmap) in &mapping {
-                    if vmap.contains(k) {
-                        km.insert(kmap.clone(), false);
-                    }
-                }
+        let mut inputs = vec![vec![]; names.len()];
+        for (from, targets) in outputs.iter().enumerate() {
+            for &to in targets {
+                inputs[to].push(from);
             }
-        });
-
-        Day20 {
-            mapping,
-            states
         }
+        Ok(Network { names, modules, outputs, inputs, button })
     }
 
-    fn reset_state(&mut self) {
-        self.states.iter_mut().for_each(|(_, v)| {
-            v.ff = false;
-            if let Some(con) = &mut v.con {
-                con.values_mut().for_each(|x| {
-                    *x = false;
-                });
-            }
-        });
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
     }
 
-    fn get_neg_pos_count(&mut self) -> (i32, i32) {
-        let mut queue:VecDeque<(String, bool, String)> = VecDeque::new();
-        let low_sigs = self.mapping.get("broadcaster").unwrap();
-        for sig in low_sigs {
-            queue.push_back((String::from("broadcaster"), false, sig.clone()));
+    pub fn id(&self, name: &str) -> Option<ModuleId> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, id: ModuleId) -> &str {
+        self.names.name(id)
+    }
+
+    pub fn module(&self, id: ModuleId) -> Module {
+        self.modules[id]
+    }
+
+    pub fn outputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.outputs[id]
+    }
+
+    pub fn inputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.inputs[id]
+    }
+
+    /// Every flip-flop off and every conjunction remembering only low pulses.
+    pub fn initial_state(&self) -> State {
+        State {
+            on: vec![false; self.len()],
+            memory: self.inputs.iter().map(|inputs| vec![false; inputs.len()]).collect(),
         }
-        let mut neg = 0;
-        let mut pos = 0;
-        while let Some((f_node, pulse, node)) = queue.pop_front() {
-            if pulse {
-                pos += 1;
-            } else {
-                neg += 1;
+    }
+
+    /// Presses the button once, handing each pulse to `on_pulse` in the order they are
+    /// sent, starting with the button's own.
+    pub fn press_with(&self, state: &mut State, mut on_pulse: impl FnMut(&Pulse)) {
+        let mut queue = VecDeque::from([Pulse { from: self.button, to: self.outputs[self.button][0], high: false }]);
+        while let Some(pulse) = queue.pop_front() {
+            on_pulse(&pulse);
+            let Pulse { from, to, high } = pulse;
+            let sent = match self.modules[to] {
+                Module::Broadcaster => high,
+                Module::FlipFlop if !high => {
+                    state.on[to] = !state.on[to];
+                    state.on[to]
+                }
+                Module::Conjunction => {
+                    let slot = self.inputs[to].iter().position(|&input| input == from).unwrap();
+                    state.memory[to][slot] = high;
+                    !state.memory[to].iter().all(|&h| h)
+                }
+                Module::FlipFlop | Module::Button | Module::Sink => continue,
+            };
+            queue.extend(self.outputs[to].iter().map(|&next| Pulse { from: to, to: next, high: sent }));
+        }
+    }
+
+    /// Presses the button once, returning every pulse sent in order.
+    pub fn press(&self, state: &mut State) -> Vec<Pulse> {
+        let mut trace = Vec::new();
+        self.press_with(state, |pulse| trace.push(*pulse));
+        trace
+    }
+
+    /// Keeps pressing until a press sends a pulse `watch` picks out, returning how many
+    /// presses that took. `None` when `limit` presses go by without one.
+    pub fn presses_until(&self, state: &mut State, mut watch: impl FnMut(&Pulse) -> bool, limit: u64) -> Option<u64> {
+        (1..=limit).find(|_| {
+            let mut seen = false;
+            self.press_with(state, |pulse| seen |= watch(pulse));
+            seen
+        })
+    }
+
+    /// The first press after which the state repeats an earlier one, and how many presses
+    /// apart the two are. `None` when no state repeats within `limit` presses.
+    pub fn state_cycle(&self, limit: u64) -> Option<(u64, u64)> {
+        let mut state = self.initial_state();
+        let mut seen = HashMap::from([(state.clone(), 0)]);
+        for presses in 1..=limit {
+            self.press_with(&mut state, |_| ());
+            if let Some(previous) = seen.insert(state.clone(), presses) {
+                return Some((previous, presses - previous));
             }
-            self.update_states(&mut queue, f_node, pulse, &node);
         }
-        (pos, neg + 1)
+        None
     }
 
-    fn find_node(&mut self, needle: &str) -> bool {
-        let mut queue:VecDeque<(String, bool, String)> = VecDeque::new();
-        let low_sigs = self.mapping.get("broadcaster").unwrap();
-        for sig in low_sigs {
-            queue.push_back((String::from("broadcaster"), false, sig.clone()));
+    /// The wiring in Graphviz DOT, with flip-flops as boxes and conjunctions as diamonds.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n");
+        for (id, module) in self.modules.iter().enumerate() {
+            let shape = match module {
+                Module::Button | Module::Broadcaster => "ellipse",
+                Module::FlipFlop => "box",
+                Module::Conjunction => "diamond",
+                Module::Sink => "doublecircle",
+            };
+            writeln!(dot, "    \"{}\" [shape={shape}];", self.name(id)).unwrap();
         }
-        while let Some((f_node, pulse, node)) = queue.pop_front() {
-            if f_node == needle && pulse {
-                return true;
+        for (from, targets) in self.outputs.iter().enumerate() {
+            for &to in targets {
+                writeln!(dot, "    \"{}\" -> \"{}\";", self.name(from), self.name(to)).unwrap();
             }
-            self.update_states(&mut queue, f_node, pulse, &node);
         }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub struct Day20 {
+    network: Network,
+}
+
+impl Day20 {
+    pub fn new() -> Self {
+        parse::load("data/year2023/day20", Self::parse)
+    }
 
-        false
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Day20 { network: Network::parse(input)? })
     }
 
-    fn update_states(&mut self, queue: &mut VecDeque<(String, bool, String)>, f_node: String, pulse: bool, node: &String) {
-        if let Some(state) = self.states.get_mut(node) {
-            if let Some(con) = &mut state.con {
-                con.insert(f_node, pulse);
-                let new_pulse = !con.values().all(|x| *x);
-                for i in self.mapping.get(node).unwrap() {
-                    queue.push_back((node.clone(), new_pulse, i.clone()));
-                }
-            } else if !pulse {
-                let new_pulse = !state.ff;
-                state.ff = new_pulse;
-                for i in self.mapping.get(node).unwrap() {
-                    queue.push_back((node.clone(), new_pulse, i.clone()));
+    /// How many presses apart each of `nodes` sends a high pulse, checking the first high
+    /// comes after that many presses and the next one after twice as many.
+    fn high_cycles(&self, nodes: &[ModuleId]) -> Result<Vec<u64>, String> {
+        let mut highs: HashMap<ModuleId, Vec<u64>> = nodes.iter().map(|&n| (n, Vec::new())).collect();
+        let mut state = self.network.initial_state();
+        for presses in 1..=MAX_PRESSES {
+            self.network.press_with(&mut state, |pulse| {
+                if let Some(seen) = highs.get_mut(&pulse.from).filter(|_| pulse.high) {
+                    if seen.last() != Some(&presses) {
+                        seen.push(presses);
+                    }
                 }
+            });
+            if highs.values().all(|seen| seen.len() >= 2) {
+                break;
             }
         }
+        nodes
+            .iter()
+            .map(|node| {
+                let name = self.network.name(*node);
+                match highs[node][..] {
+                    [first, second, ..] if second == 2 * first => Ok(first),
+                    [first, second, ..] => Err(format!("{name} sends high after {first} and {second} presses, which is no clean cycle")),
+                    _ => Err(format!("{name} does not send high twice in {MAX_PRESSES} presses")),
+                }
+            })
+            .collect()
     }
 }
 
 impl Day for Day20 {
-    fn part_1(&mut self) -> u64 {
-        self.reset_state();
+    fn part_1(&self) -> Answer {
+        let mut state = self.network.initial_state();
         let mut p = 0_u64;
         let mut n = 0_u64;
         for _ in 0..1000 {
-            let (a, b) = self.get_neg_pos_count();
-            p += a as u64;
-            n += b as u64;
+            self.network.press_with(&mut state, |pulse| if pulse.high { p += 1 } else { n += 1 });
         }
-        p * n
+        (p * n).into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let nodes = ["tx", "dd", "nz", "ph"];
-        let mut vals: Vec<i32> = Vec::new();
+    fn part_2(&self) -> Answer {
+        // `rx` hangs off a single conjunction, which sends it a low pulse once every one of
+        // its inputs has last sent a high. Each input does so on a cycle of its own.
+        let rx = self.network.id("rx").expect("No module sends to rx");
+        let &[last] = self.network.inputs(rx) else {
+            panic!("Expected exactly one module sending to rx");
+        };
+        assert_eq!(
+            self.network.module(last),
+            Module::Conjunction,
+            "{} sends to rx but is not a conjunction",
+            self.network.name(last)
+        );
+        let cycles = self.high_cycles(self.network.inputs(last)).unwrap_or_else(|err| panic!("{err}"));
+        lcm_all(cycles).into()
+    }
+}
 
-        for node in nodes {
-            self.reset_state();
-            let mut count = 1;
-            while !self.find_node(node) {
-                count += 1;
-            }
-            vals.push(count);
-        }
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::traits::Day;
+    use crate::year2023::day20::{Day20, Module, Network, Pulse};
+
+    /// Two counters of flip-flops, one and two long, each read out through an inverter.
+    const COUNTERS: &str = "broadcaster -> a, b1
+%a -> ia
+&ia -> m
+%b1 -> b2
+%b2 -> ib
+&ib -> m
+&m -> rx";
+
+    const SAMPLE: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn test_rx_feeders() {
+        let day = Day20::parse(COUNTERS).unwrap();
+        let network = &day.network;
+        let m = network.id("m").unwrap();
+        assert_eq!(network.inputs(network.id("rx").unwrap()), &[m]);
+        let feeders = network.inputs(m);
+        assert_eq!(day.high_cycles(feeders), Ok(vec![2, 4]));
+        assert!(day.high_cycles(&[network.id("b1").unwrap()]).is_err());
 
-        Day8::lcm(vals)
+        // Pressing until `rx` sees a low pulse agrees.
+        let rx = network.id("rx").unwrap();
+        let presses = network.presses_until(&mut network.initial_state(), |p| p.to == rx && !p.high, 100);
+        assert_eq!(Some(day.part_2()), presses.map(Answer::from));
     }
-}
 
+    #[test]
+    fn test_trace() {
+        let network = Network::parse(SAMPLE).unwrap();
+        let output = network.id("output").unwrap();
+        assert_eq!(network.module(output), Module::Sink);
+        assert_eq!(network.module(network.id("con").unwrap()), Module::Conjunction);
+
+        let mut state = network.initial_state();
+        let trace = network.press(&mut state);
+        let names: Vec<String> = trace
+            .iter()
+            .map(|p| format!("{} -{}-> {}", network.name(p.from), if p.high { "high" } else { "low" }, network.name(p.to)))
+            .collect();
+        assert_eq!(names, [
+            "button -low-> broadcaster",
+            "broadcaster -low-> a",
+            "a -high-> inv",
+            "a -high-> con",
+            "inv -low-> b",
+            "con -high-> output",
+            "b -high-> con",
+            "con -low-> output",
+        ]);
+        assert_eq!(trace.last(), Some(&Pulse { from: network.id("con").unwrap(), to: output, high: false }));
+
+        // Four presses take every module back to where it started.
+        assert_eq!(network.state_cycle(10), Some((0, 4)));
+        assert!(network.to_dot().contains("\"inv\" -> \"b\";"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Network::parse("%a -> b").is_err());
+        assert!(Network::parse("broadcaster -> a\n?a -> b").is_err());
+    }
+}
@@ -1,134 +1,161 @@
-use std::collections::{HashSet, VecDeque};
-use std::fs;
-use std::hash::{Hash, Hasher};
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::math::lcm;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
+/// How many periods in a row must add the same amount again before the growth counts as
+/// settled into a quadratic.
+const SETTLED_PERIODS: usize = 4;
+/// The furthest the search for quadratic growth walks before giving up.
+const MAX_DEPTH: u64 = 20_000;
+
 pub struct Day21 {
-    lines: Vec<Vec<char>>,
-    x_lim: i32,
-    y_lim: i32,
+    lines: Grid<char>,
     start: Point
 }
 
+impl Day21 {
+    pub fn new() -> Self {
+        parse::load("data/year2023/day21", Self::parse)
+    }
 
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = Grid::parse(input, input, ".#S")?;
+        let Some(start) = lines.find(&'S') else {
+            return Err(ParseError::at(input, input, "a start position `S`"));
+        };
 
-#[derive(Clone, Copy, Debug)]
-pub struct Point {
-    x: i32,
-    y: i32,
-    distance: u32,
-}
-
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+        Ok(Day21 {
+            lines,
+            start,
+        })
     }
-}
-
-impl Eq for Point {}
 
-impl Hash for Point {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
+    /// Tile at `point` of the garden repeating infinitely.
+    fn get_val(&self, point: Point) -> char {
+        *self.lines.get_wrapping(point)
     }
-}
 
-impl Point {
-    fn new(x: i32, y: i32, distance: u32) -> Self {
-        Point { x, y, distance }
+    /// How many steps the garden takes to repeat in both directions.
+    fn period(&self) -> u64 {
+        lcm(self.lines.width(), self.lines.height()) as u64
     }
-}
 
-impl Day21 {
-    pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day21")
-            .expect("Cannot read file")
-            .split('\n')
-            .map(|x| x.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        let mut x = 0;
-        let mut y = 0;
-        let mut found = false;
-
-        for (i, row) in lines.iter().enumerate() {
-            for (j, c) in row.iter().enumerate() {
-                if *c == 'S' {
-                    x = i;
-                    y = j;
-                    found = true;
-                    break;
+    /// How many plots of the infinite garden are first reached after each number of steps,
+    /// up to `depth`. Walks a block of garden repeats just large enough to hold them.
+    fn first_reached(&self, depth: u64) -> Vec<u64> {
+        let (width, height) = (self.lines.width() as i32, self.lines.height() as i32);
+        let repeats = depth as i32 / width.min(height) + 1;
+        let (columns, rows) = ((2 * repeats + 1) * width, (2 * repeats + 1) * height);
+        let index = |p: Point| (p.x >= 0 && p.y >= 0 && p.x < columns && p.y < rows).then(|| (p.y * columns + p.x) as usize);
+
+        // The garden repeats, so starting a whole number of repeats in sees the same plots.
+        let origin = self.start.offset(repeats * width, repeats * height);
+        let mut seen = vec![false; (columns * rows) as usize];
+        seen[index(origin).unwrap()] = true;
+        let mut frontier = vec![origin];
+        let mut counts = Vec::new();
+        while !frontier.is_empty() && counts.len() as u64 <= depth {
+            counts.push(frontier.len() as u64);
+            let mut next = Vec::new();
+            for point in frontier {
+                for neighbour in point.neighbours4() {
+                    let Some(i) = index(neighbour) else { continue };
+                    if !seen[i] && self.get_val(neighbour) != '#' {
+                        seen[i] = true;
+                        next.push(neighbour);
+                    }
                 }
             }
-            if found {
-                break;
-            }
-        }
-        let start = Point::new(x as i32, y as i32, 0);
-        let x_lim = lines.len() as i32;
-        let y_lim = lines[0].len() as i32;
-
-        Day21 {
-            lines,
-            start,
-            x_lim,
-            y_lim
+            frontier = next;
         }
+        counts
     }
 
-    pub fn get_val(&self, mut x:i32, mut y:i32) -> char {
-        x %= self.x_lim;
-        y %= self.y_lim;
-        if x < 0 {
-            x += self.x_lim;
-        }
-        if y < 0 {
-            y += self.y_lim;
-        }
-        self.lines[x as usize][y as usize]
+    /// Plots reachable in exactly `steps`, given `first_reached` out to at least that many.
+    /// A plot first reached an even number of steps short can be returned to by stepping
+    /// back and forth.
+    fn reachable_in(counts: &[u64], steps: u64) -> u64 {
+        counts.iter().take(steps as usize + 1).skip(steps as usize % 2).step_by(2).sum()
     }
 
-    pub fn walk(&mut self, limit: u32) -> u64 {
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(self.start);
-        let mut count = 0;
-
-        while let Some(mut node) = queue.pop_front() {
-            if visited.contains(&node) {
-                continue;
-            }
-            visited.insert(node);
-
-            if node.distance == limit {
-                count += 1;
-                continue;
-            } else if (limit - node.distance) % 2 == 0 {
-                count += 1;
+    /// How many plots of the infinite garden can be stood on after exactly `steps`.
+    ///
+    /// Counts directly for the steps left over after whole periods of the garden, then one,
+    /// two and more periods on. Once each period adds the same amount more than the one
+    /// before, the counts follow a quadratic out to any number of periods.
+    pub fn reachable_after(&self, steps: u64) -> u64 {
+        let period = self.period();
+        let remainder = steps % period;
+        let target = steps / period;
+        let mut depth = remainder + (SETTLED_PERIODS as u64 + 2) * period;
+        loop {
+            let counts = self.first_reached(depth);
+            let terms: Vec<u64> = (remainder..=depth).step_by(period as usize).map(|s| Day21::reachable_in(&counts, s)).collect();
+            if target < terms.len() as u64 {
+                return terms[target as usize];
             }
-            node.distance += 1;
-            let mut points = vec![node; 4];
-            points[0].x -= 1;
-            points[1].x += 1;
-            points[2].y += 1;
-            points[3].y -= 1;
-            for next_point in points {
-                if self.get_val(next_point.x, next_point.y) != '#' {
-                    queue.push_back(next_point);
-                }
+            let second: Vec<i64> = terms.windows(3).map(|w| w[2] as i64 - 2 * w[1] as i64 + w[0] as i64).collect();
+            let settled = second.windows(SETTLED_PERIODS).position(|run| run.iter().all(|&d| d == run[0]));
+            if let Some(k) = settled {
+                let (first, step, growth) = (terms[k] as i64, terms[k + 1] as i64 - terms[k] as i64, second[k]);
+                let n = (target - k as u64) as i64;
+                return (first + n * step + n * (n - 1) / 2 * growth) as u64;
             }
+            assert!(depth < MAX_DEPTH, "Reachable plots never settle into quadratic growth");
+            depth *= 2;
         }
-        count
     }
 }
 
 impl Day for Day21 {
-    fn part_1(&mut self) -> u64 {
-        self.walk(64)
+    fn part_1(&self) -> Answer {
+        self.reachable_after(64).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        self.reachable_after(26501365).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::year2023::day21::Day21;
+
+    const SAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day21::parse(SAMPLE).unwrap();
+        for (steps, plots) in [(6, 16), (10, 50), (50, 1594), (100, 6536), (500, 167004), (1000, 668697), (5000, 16733044)] {
+            assert_eq!(day.reachable_after(steps), plots, "{steps} steps");
+        }
     }
 
-    fn part_2(&mut self) -> u64 {
-        self.walk(26501365)
+    #[test]
+    fn test_matches_brute_force() {
+        let day = Day21::parse(SAMPLE).unwrap();
+        // Every plot reachable, one step at a time.
+        let mut plots = HashSet::from([day.start]);
+        for steps in 0..=100 {
+            assert_eq!(day.reachable_after(steps), plots.len() as u64, "{steps} steps");
+            plots = plots.iter().flat_map(|p| p.neighbours4()).filter(|&p| day.get_val(p) != '#').collect();
+        }
+        assert_eq!(day.reachable_after(5000), 16733044);
     }
 }
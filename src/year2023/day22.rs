@@ -1,198 +1,235 @@
-use std::collections::HashSet;
-use std::fs;
+use std::fmt;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
+/// The cubes from `start` to `end` inclusive, with `start` the lower corner on every axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brick {
+    pub start: [usize; 3],
+    pub end: [usize; 3],
+}
+
+impl Brick {
+    /// Every `(x, y)` the brick covers when seen from above.
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start[1]..=self.end[1]).flat_map(move |y| (self.start[0]..=self.end[0]).map(move |x| (x, y)))
+    }
+}
+
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x1, y1, z1] = self.start;
+        let [x2, y2, z2] = self.end;
+        write!(f, "{x1},{y1},{z1}~{x2},{y2},{z2}")
+    }
+}
+
 pub struct Day22 {
-    bricks: Vec<([usize; 3], [usize; 3])>
+    /// The bricks after they have all fallen, in the order they came to rest.
+    bricks: Vec<Brick>,
+    /// For each brick, the bricks directly beneath that it rests on.
+    below: Vec<Vec<usize>>,
+    /// For each brick, the bricks resting directly on top of it.
+    above: Vec<Vec<usize>>,
 }
 
 impl Day22 {
     pub fn new() -> Self {
-        let mut bricks = fs::read_to_string("data/year2023/day22")
-            .expect("Cannot read file")
-            .split('\n')
+        parse::load("data/year2023/day22", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let bricks = input
+            .lines()
             .map(|line| {
-                let (start, end) = line.split_once('~').expect("Line malformed");
-                (Day22::parse_point(start).unwrap_or_else(|err| panic!("Error: {err}")),
-                 Day22::parse_point(end).unwrap_or_else(|err| panic!("Error: {err}")))
-            }).collect::<Vec<_>>();
-
-        bricks.sort_by(|a, b| a.0[2].cmp(&b.0[2]));
-        Day22 {
-            bricks
-        }
+                let (start_str, end_str) = parse::split_once(input, line, "~")?;
+                let (start, end) = (Day22::parse_point(input, start_str)?, Day22::parse_point(input, end_str)?);
+                // The ground is at 0, so every cube of a brick sits above it.
+                for (point, point_str) in [(start, start_str), (end, end_str)] {
+                    if point[2] == 0 {
+                        return Err(ParseError::at(input, point_str, "a point with z of at least 1"));
+                    }
+                }
+                Ok(Brick { start: [0, 1, 2].map(|i| start[i].min(end[i])), end: [0, 1, 2].map(|i| start[i].max(end[i])) })
+            }).collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(Day22::settle(bricks))
     }
 
-    pub fn parse_point(point_str: &str) -> Result<[usize; 3], String> {
+    pub fn parse_point(input: &str, point_str: &str) -> Result<[usize; 3], ParseError> {
         let parts:Vec<&str> = point_str.split(",").collect();
         if parts.len() != 3 {
-            Err(format!("String {point_str} does not have 3 points"))
+            Err(ParseError::at(input, point_str, "a point like `1,0,1`"))
         } else {
             let mut points = [0; 3];
             for (i, &point) in parts.iter().enumerate() {
-                match point.parse::<usize>() {
-                    Ok(num) => points[i] = num,
-                    Err(_) => return Err(format!("Point {point} is not a valid number"))
-                }
+                points[i] = parse::number(input, point)?;
             }
 
             Ok(points)
         }
     }
 
-    fn settle(&mut self) {
-        let roof =  self.bricks[self.bricks.len()-1].0[2];
-        let mut occupied_tiles: Vec<HashSet<(usize, usize)>> = vec![HashSet::new(); roof];
-        for level in 1..=roof {
-            self.bricks.iter_mut().filter(|x| x.0[2] == level)
-                .for_each(|(start, end)| {
-                    //         check the lowest level that it fits
-                    let mut settle_floor = level - 1;
-                    for (i, floor) in occupied_tiles[0..level].iter_mut().enumerate().rev() {
-                        if !Day22::can_brick_fit_in_floor(floor, start, end) {
-                            break;
-                        }
-                        settle_floor = i;
-                    }
-                    let jump = start[2] - settle_floor;
-                    start[2] -= jump;
-                    end[2] -= jump;
-
-                    //         update occupied_tiles
-                    for tile in occupied_tiles[start[2]..=end[2]].iter_mut() {
-                        for y in start[1]..=end[1] {
-                            for x in start[0]..=end[0] {
-                                tile.insert((x, y));
-                            }
-                        }
+    /// Drops the bricks lowest first onto a height map of the pile so far, noting which
+    /// bricks each one lands on. The ground is at height 0, so the lowest bricks end up at 1.
+    fn settle(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort_by_key(|brick| brick.start[2]);
+        let width = bricks.iter().map(|brick| brick.end[0] + 1).max().unwrap_or(0);
+        let depth = bricks.iter().map(|brick| brick.end[1] + 1).max().unwrap_or(0);
+        // The height of the top of the pile at each `(x, y)`, and the brick there if any.
+        let mut tops: Vec<Vec<(usize, Option<usize>)>> = vec![vec![(0, None); width]; depth];
+        let mut below = vec![vec![]; bricks.len()];
+        let mut above = vec![vec![]; bricks.len()];
+
+        for (i, brick) in bricks.iter_mut().enumerate() {
+            let rest = brick.footprint().map(|(x, y)| tops[y][x].0).max().unwrap_or(0);
+            for (x, y) in brick.footprint() {
+                if let (top, Some(under)) = tops[y][x] {
+                    if top == rest && !below[i].contains(&under) {
+                        below[i].push(under);
+                        above[under].push(i);
                     }
-                });
-        }
-    }
-
-    pub fn can_brick_fit_in_floor(floor: &HashSet<(usize, usize)>, start:&[usize; 3], end:&[usize; 3]) -> bool {
-        for x in start[0]..=end[0] {
-            for y in start[1]..=end[1] {
-                if floor.contains(&(x, y)) {
-                    return false;
                 }
             }
+            let fall = brick.start[2] - (rest + 1);
+            brick.start[2] -= fall;
+            brick.end[2] -= fall;
+            for (x, y) in brick.footprint() {
+                tops[y][x] = (brick.end[2], Some(i));
+            }
         }
-        true
+        Day22 { bricks, below, above }
     }
 
-    fn is_only_leaf(graph: &Vec<Vec<usize>>, start: usize, leaf: usize) -> bool {
-        let mut stack = vec![start];
-        let mut leafs: HashSet<usize> = HashSet::new();
-        while let Some(node) = stack.pop() {
-            if graph[node].is_empty() || node == leaf {
-                leafs.insert(node);
-                continue;
-            }
-            for g in &graph[node] {
-                stack.push(*g);
+    /// The settled bricks, one per line in the same `x,y,z~x,y,z` form as the input.
+    pub fn layout(&self) -> String {
+        self.bricks.iter().map(|brick| format!("{brick}\n")).collect()
+    }
+
+    /// The bricks that can be taken away without any other brick falling, because every
+    /// brick resting on them rests on another one too.
+    pub fn safe_bricks(&self) -> Vec<usize> {
+        (0..self.bricks.len())
+            .filter(|&i| self.above[i].iter().all(|&a| self.below[a].len() > 1))
+            .collect()
+    }
+
+    /// How many other bricks fall when `brick` is taken away. A brick falls once every
+    /// brick it rests on has, so each one counts down its supports still standing.
+    pub fn chain_reaction(&self, brick: usize) -> usize {
+        let mut standing: Vec<usize> = self.below.iter().map(Vec::len).collect();
+        let mut falling = vec![brick];
+        let mut fallen = 0;
+        while let Some(i) = falling.pop() {
+            for &a in &self.above[i] {
+                standing[a] -= 1;
+                if standing[a] == 0 {
+                    fallen += 1;
+                    falling.push(a);
+                }
             }
         }
-        leafs.len() == 1 && leafs.contains(&leaf)
+        fallen
     }
 
-    fn count_nodes_destroyed(graph: &Vec<Vec<usize>>, rev_graph: &[Vec<usize>], node: usize) -> u64 {
-        let mut stack = vec![node];
-        let mut nodes: HashSet<usize> = HashSet::new();
-        while let Some(curr) = stack.pop() {
-            nodes.insert(curr);
-            for nxt in &rev_graph[curr] {
-                if Day22::is_only_leaf(graph, curr, node) {
-                    stack.push(*nxt);
+    /// The sum of `chain_reaction` over every brick. Taking away a brick drops exactly the
+    /// bricks it dominates, those whose every way down to the ground passes through it, so
+    /// each brick is counted once for each brick above the ground in its dominator tree.
+    pub fn total_chain_reaction(&self) -> usize {
+        // Immediate dominators and depths, `None` being the ground at depth 0. Bricks only
+        // rest on bricks that settled before them, so these are always known in time.
+        let mut parent: Vec<Option<usize>> = Vec::with_capacity(self.bricks.len());
+        let mut depth: Vec<usize> = Vec::with_capacity(self.bricks.len());
+        let depth_of = |node: Option<usize>, depth: &[usize]| node.map_or(0, |n| depth[n]);
+        for below in &self.below {
+            let dominator = below.iter().map(|&b| Some(b)).reduce(|mut a, mut b| {
+                // Their lowest common ancestor in the dominator tree.
+                while a != b {
+                    if depth_of(a, &depth) >= depth_of(b, &depth) {
+                        a = a.and_then(|n| parent[n]);
+                    } else {
+                        b = b.and_then(|n| parent[n]);
+                    }
                 }
-            }
+                a
+            });
+            let dominator = dominator.flatten();
+            depth.push(depth_of(dominator, &depth) + 1);
+            parent.push(dominator);
         }
+        depth.iter().map(|d| d - 1).sum()
+    }
+}
 
-        (nodes.len() - 1) as u64
+impl Day for Day22 {
+    fn part_1(&self) -> Answer {
+        self.safe_bricks().len().into()
     }
 
+    fn part_2(&self) -> Answer {
+        self.total_chain_reaction().into()
+    }
 }
 
-impl Day for Day22 {
-    fn part_1(&mut self) -> u64 {
-        self.settle();
-        let count = self.bricks.len();
-        let mut brick_index:HashSet<usize> = HashSet::new();
-
-        for (start, end) in self.bricks.iter().rev() {
-            if start[2] == 0 {
-                continue;
-            }
-            let mut points = vec![];
-            for x in start[0]..=end[0] {
-                for y in start[1]..=end[1] {
-                    points.push((x, y));
-                }
-            }
-            let floor_below = start[2] - 1;
-            let supporting_bricks = self.bricks.iter().enumerate()
-                .filter(|(_, x)| x.0[2] == floor_below || x.1[2] == floor_below)
-                .filter_map(|(i, (start, end))| {
-                    for x in start[0]..=end[0] {
-                        for y in start[1]..=end[1] {
-                            if points.contains(&(x, y)) {
-                                return Some(i)
-                            }
-                        }
-                    }
-                   None
-                }).collect::<Vec<_>>();
-            if supporting_bricks.len() == 1 {
-                brick_index.insert(supporting_bricks[0]);
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::year2023::day22::Day22;
+
+    const SAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day22::parse(SAMPLE).unwrap();
+        assert_eq!(day.layout(), "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,2~2,2,2
+0,0,3~0,2,3
+2,0,3~2,2,3
+0,1,4~2,1,4
+1,1,5~1,1,6
+");
+        assert_eq!(day.safe_bricks(), vec![1, 2, 3, 4, 6]);
+        assert_eq!(day.chain_reaction(0), 6);
+        assert_eq!(day.chain_reaction(5), 1);
+        assert_eq!(day.total_chain_reaction(), 7);
+    }
 
-        (count - brick_index.len()) as u64
+    #[test]
+    fn test_parse_error() {
+        let err = Day22::parse("1,0,1~1,2,1\n0,0,0~0,0,1\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "0,0,0"));
     }
 
-    fn part_2(&mut self) -> u64 {
-        self.settle();
-        let mut brick_index:HashSet<usize> = HashSet::new();
-        let mut adjacency_list: Vec<Vec<usize>> = vec![vec![]; self.bricks.len()];
-        for (i, (start, end)) in self.bricks.iter().enumerate().rev() {
-            if start[2] == 0 {
-                continue;
-            }
-            let mut points = vec![];
-            for x in start[0]..=end[0] {
-                for y in start[1]..=end[1] {
-                    points.push((x, y));
-                }
-            }
-            let floor_below = start[2] - 1;
-            let supporting_bricks = self.bricks.iter().enumerate()
-                .filter(|(_, x)| x.0[2] == floor_below || x.1[2] == floor_below)
-                .filter_map(|(i, (start, end))| {
-                    for x in start[0]..=end[0] {
-                        for y in start[1]..=end[1] {
-                            if points.contains(&(x, y)) {
-                                return Some(i)
-                            }
-                        }
-                    }
-                    None
-                }).collect::<Vec<_>>();
-            if supporting_bricks.len() == 1 {
-                brick_index.insert(supporting_bricks[0]);
-            }
-            adjacency_list[i] = supporting_bricks;
-        }
-        let mut reverse_graph:Vec<Vec<usize>> = vec![vec![]; self.bricks.len()];
-        for (i, adj) in adjacency_list.iter().enumerate() {
-            for a in adj {
-                reverse_graph[*a].push(i);
-            }
-        }
-        let mut count = 0;
-        for i in brick_index {
-            let res = Day22::count_nodes_destroyed(&adjacency_list, &reverse_graph, i);
-            count += res;
-        }
-        count
+    #[test]
+    fn test_dominators_match_chain_reactions() {
+        let mut rng = StdRng::seed_from_u64(22);
+        let input: String = (0..300)
+            .map(|_| {
+                let (x, y, z) = (rng.gen_range(0..6), rng.gen_range(0..6), rng.gen_range(1..200));
+                let [dx, dy, dz] = match rng.gen_range(0..3) {
+                    0 => [rng.gen_range(0..3), 0, 0],
+                    1 => [0, rng.gen_range(0..3), 0],
+                    _ => [0, 0, rng.gen_range(0..3)],
+                };
+                format!("{x},{y},{z}~{},{},{}\n", x + dx, y + dy, z + dz)
+            })
+            .collect();
+        let day = Day22::parse(&input).unwrap();
+        let total: usize = (0..day.bricks.len()).map(|i| day.chain_reaction(i)).sum();
+        assert!(total > 0);
+        assert_eq!(day.total_chain_reaction(), total);
+        let safe: Vec<usize> = (0..day.bricks.len()).filter(|&i| day.chain_reaction(i) == 0).collect();
+        assert_eq!(day.safe_bricks(), safe);
     }
 }
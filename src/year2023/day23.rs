@@ -1,244 +1,230 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap};
-use std::fs;
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use crate::answer::Answer;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
-enum Direction {
-    West,
-    East,
-    North,
-    South
+use rayon::prelude::*;
+
+/// How the slopes `^>v<` are walked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slopes {
+    /// Icy: a slope is only left downhill and never climbed.
+    OneWay,
+    /// Dry: slopes are walked like any other path tile.
+    Flat,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Point {
-    x: usize,
-    y: usize,
-    direction: Direction,
-    distance: usize
+/// The trails reduced to their junctions, along with the start and end, and the length of
+/// each corridor between them.
+#[derive(Debug, Clone)]
+pub struct Junctions {
+    points: Vec<Point>,
+    /// For each junction, the junctions a corridor leads on to and its length in steps.
+    corridors: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
 }
 
-impl Point {
-    fn can_go_north(&self) -> bool {
-        self.x > 0
+impl Junctions {
+    pub fn len(&self) -> usize {
+        self.points.len()
     }
 
-    fn can_go_west(&self) -> bool {
-        self.y > 0
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
     }
 
-    fn can_go_east(&self, y_lim: usize) -> bool {
-        self.y < y_lim - 1
+    pub fn point(&self, junction: usize) -> Point {
+        self.points[junction]
     }
 
-    fn can_go_south(&self, x_lim: usize) -> bool {
-        self.x < x_lim - 1
+    pub fn corridors(&self, junction: usize) -> &[(usize, usize)] {
+        &self.corridors[junction]
     }
 
-    fn go_north(&self) -> Point {
-        Point {
-            x: self.x - 1,
-            y: self.y,
-            direction: Direction::North,
-            distance: self.distance + 1
-        }
-    }
+    /// The most steps a hike from the start to the end can take without visiting any tile
+    /// twice, or `None` when the end is out of reach. With `parallel`, the hikes are split
+    /// between threads at the first junction offering a choice.
+    pub fn longest_path(&self, parallel: bool) -> Option<usize> {
+        assert!(self.len() <= 64, "{} junctions do not fit a u64 of visited ones", self.len());
+        // Whichever junction is the only way into the end must go there, or the end is lost.
+        let mut into_end = (0..self.len()).filter(|&j| self.corridors[j].iter().any(|&(to, _)| to == self.end));
+        let last = match (into_end.next(), into_end.next()) {
+            (Some(last), None) => self.corridors[last].iter().find(|&&(to, _)| to == self.end).map(|&(_, len)| (last, len)),
+            _ => None,
+        };
 
-    fn go_south(&self) -> Point {
-        Point {
-            x: self.x + 1,
-            y: self.y,
-            direction: Direction::South,
-            distance: self.distance + 1
+        let mut hikes = vec![(self.start, 1_u64 << self.start, 0)];
+        if parallel {
+            while let [(junction, visited, steps)] = hikes[..] {
+                if junction == self.end || last.is_some_and(|(last, _)| last == junction) {
+                    break;
+                }
+                hikes = self.onward(junction, visited, steps).collect();
+            }
+            hikes.into_par_iter().filter_map(|(junction, visited, steps)| self.hike(junction, visited, steps, last)).max()
+        } else {
+            hikes.into_iter().filter_map(|(junction, visited, steps)| self.hike(junction, visited, steps, last)).max()
         }
     }
 
-    fn go_west(&self) -> Point {
-        Point {
-            x: self.x,
-            y: self.y - 1,
-            direction: Direction::West,
-            distance: self.distance + 1
-        }
+    /// Every junction one corridor on from `junction` not yet `visited`, as a hike there.
+    fn onward(&self, junction: usize, visited: u64, steps: usize) -> impl Iterator<Item = (usize, u64, usize)> + '_ {
+        self.corridors[junction]
+            .iter()
+            .filter(move |&&(to, _)| visited & (1 << to) == 0)
+            .map(move |&(to, len)| (to, visited | 1 << to, steps + len))
     }
 
-    fn go_east(&self) -> Point {
-        Point {
-            x: self.x,
-            y: self.y + 1,
-            direction: Direction::East,
-            distance: self.distance + 1
+    /// Longest way on to the end from `junction`, having visited the junctions in the
+    /// `visited` mask and walked `steps`.
+    fn hike(&self, junction: usize, visited: u64, steps: usize, last: Option<(usize, usize)>) -> Option<usize> {
+        if junction == self.end {
+            return Some(steps);
         }
-    }
-}
-
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-impl Eq for Point {}
-
-impl PartialOrd for Point {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Point {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.distance.cmp(&other.distance)
-    }
-}
-
-impl Hash for Point {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
+        if let Some((_, len)) = last.filter(|&(last, _)| last == junction) {
+            return Some(steps + len);
+        }
+        self.onward(junction, visited, steps)
+            .filter_map(|(to, visited, steps)| self.hike(to, visited, steps, last))
+            .max()
     }
 }
 
 pub struct Day23 {
-    graph: Vec<Vec<char>>,
-    x_lim: usize,
-    y_lim: usize
+    graph: Grid<char>,
 }
 
 impl Day23 {
     pub fn new() -> Self {
-        let graph = fs::read_to_string("data/year2023/day23")
-            .expect("Cannot read file")
-            .split('\n')
-            .map(|x| x.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let x_lim = graph.len();
-        let y_lim = graph[0].len();
-        Day23 {
-            graph,
-            x_lim,
-            y_lim
-        }
+        parse::load("data/year2023/day23", Self::parse)
     }
 
-    fn walk(&self) -> usize {
-        let mut heap: BinaryHeap<Point> = BinaryHeap::new();
-        let start = Point {x: 0, y: 1, direction: Direction::South, distance: 0};
-        let end = Point { x: self.x_lim - 1, y: self.y_lim - 2, direction: Direction::South, distance: 0 };
-        heap.push(start);
-        let mut res = 0;
-        while let Some(point) = heap.pop() {
-            if point == end {
-                if point.distance > res {
-                    res = point.distance;
-                }
-                continue;
-            }
-            let slope = match self.graph[point.x][point.y] {
-                '>' => Some(Direction::East),
-                '<' => Some(Direction::West),
-                'v' => Some(Direction::South),
-                '^' => Some(Direction::North),
-                _ => None
-            };
-        //     go south
-            if point.can_go_south(self.x_lim)
-                && point.direction != Direction::North
-                && (slope.is_none() || slope.unwrap() == Direction::South)
-            {
-                let next_point = point.go_south();
-                let tile = self.graph[next_point.x][next_point.y];
-                if tile != '#' && tile != '^' {
-                    heap.push(next_point);
-                }
-            }
-
-        //     go north
-            if point.can_go_north()
-                && point.direction != Direction::South
-                && (slope.is_none() || slope.unwrap() == Direction::North)
-            {
-                let next_point = point.go_north();
-                let tile = self.graph[next_point.x][next_point.y];
-                if tile != '#' && tile != 'v' {
-                    heap.push(next_point);
-                }
-            }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let graph = Grid::parse(input, input, "#.><^v")?;
+        Ok(Day23 {
+            graph,
+        })
+    }
 
-        //     go east
-            if point.can_go_east(self.y_lim)
-                && point.direction != Direction::West
-                && (slope.is_none() || slope.unwrap() == Direction::East)
-            {
-                let next_point = point.go_east();
-                let tile = self.graph[next_point.x][next_point.y];
-                if tile != '#' && tile != '<' {
-                    heap.push(next_point);
-                }
+    /// Whether a step from `from` towards `direction` is allowed.
+    fn can_step(&self, from: Point, direction: Direction, slopes: Slopes) -> bool {
+        let Some(&tile) = self.graph.get(from.step(direction)) else {
+            return false;
+        };
+        if tile == '#' {
+            return false;
+        }
+        // Slopes are left downhill only, and walking up onto one is not allowed either.
+        slopes == Slopes::Flat
+            || (Direction::from_char(self.graph[from]).is_none_or(|slope| slope == direction)
+                && tile != direction.reverse().arrow())
+    }
+
+    /// The junctions of the trails, meaning the start, the end and every tile with more than
+    /// two neighbouring path tiles, and the corridors joining them.
+    pub fn junctions(&self, slopes: Slopes) -> Junctions {
+        let start = Point::new(1, 0);
+        let end = Point::new(self.graph.width() as i32 - 2, self.graph.height() as i32 - 1);
+        let open = |p: &Point| self.graph.get(*p).is_some_and(|&tile| tile != '#');
+        let points: Vec<Point> = self
+            .graph
+            .positions()
+            .filter(|p| *p == start || *p == end || (open(p) && p.neighbours4().iter().filter(|n| open(n)).count() > 2))
+            .collect();
+        let index: HashMap<Point, usize> = points.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+
+        let corridors = points
+            .iter()
+            .map(|&from| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|direction| self.corridor(from, direction, slopes, &index))
+                    .collect()
+            })
+            .collect();
+        Junctions { corridors, start: index[&start], end: index[&end], points }
+    }
+
+    /// Follows the corridor leaving `from` towards `direction` to the junction at its far
+    /// end, along with its length. `None` for dead ends and corridors that cannot be walked.
+    fn corridor(&self, from: Point, mut direction: Direction, slopes: Slopes, junctions: &HashMap<Point, usize>) -> Option<(usize, usize)> {
+        let mut point = from;
+        let mut len = 0;
+        loop {
+            if !self.can_step(point, direction, slopes) {
+                return None;
             }
-
-        //     go west
-            if point.can_go_west()
-                && point.direction != Direction::East
-                && (slope.is_none() || slope.unwrap() == Direction::West)
-            {
-                let next_point = point.go_west();
-                let tile = self.graph[next_point.x][next_point.y];
-                if tile != '#' && tile != '>' {
-                    heap.push(next_point);
-                }
+            point = point.step(direction);
+            len += 1;
+            if let Some(&junction) = junctions.get(&point) {
+                return Some((junction, len));
             }
+            // Anywhere other than a junction there is only one way on.
+            let back = direction.reverse();
+            direction = Direction::ALL
+                .into_iter()
+                .find(|&d| d != back && self.graph.get(point.step(d)).is_some_and(|&tile| tile != '#'))?;
         }
-        res
     }
-
 }
 
 
 impl Day for Day23 {
-    fn part_1(&mut self) -> u64 {
-        self.walk() as u64
+    fn part_1(&self) -> Answer {
+        self.junctions(Slopes::OneWay).longest_path(false).unwrap_or(0).into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let grid = &self.graph;
-        let rows = grid.len();
-        let cols = grid[0].len();
-        let mut visited = vec![vec![false; cols]; rows];
-        let mut max_path_len = 0;
-
-        fn dfs(grid: &Vec<Vec<char>>, visited: &mut Vec<Vec<bool>>, row: usize, col: usize, current_len: usize, max_path_len: &mut usize) {
-            let rows = grid.len();
-            let cols = grid[0].len();
-            if row == rows - 1 && col == cols - 2 {
-                *max_path_len = usize::max(*max_path_len, current_len);
-                return;
-            }
-
-            let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-            for (dr, dc) in directions.iter() {
-                let new_row = row as isize + dr;
-                let new_col = col as isize + dc;
-
-                if new_row >= 0 && new_row < rows as isize && new_col >= 0 && new_col < cols as isize {
-                    let new_row = new_row as usize;
-                    let new_col = new_col as usize;
-                    if grid[new_row][new_col] != '#' && !visited[new_row][new_col] {
-                        visited[new_row][new_col] = true;
-                        dfs(grid, visited, new_row, new_col, current_len + 1, max_path_len);
-                        visited[new_row][new_col] = false;
-                    }
-                }
-            }
-        }
-
-        if grid[0][1] != '#' {
-            visited[0][1] = true;
-            dfs(grid, &mut visited, 0, 1, 0, &mut max_path_len);
-        }
+    fn part_2(&self) -> Answer {
+        self.junctions(Slopes::Flat).longest_path(true).unwrap_or(0).into()
+    }
+}
 
-        max_path_len as u64
+#[cfg(test)]
+mod tests {
+    use crate::geom::Point;
+    use crate::year2023::day23::{Day23, Slopes};
+
+    const SAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day23::parse(SAMPLE).unwrap();
+        let icy = day.junctions(Slopes::OneWay);
+        let dry = day.junctions(Slopes::Flat);
+        // The start, the end and seven junctions in between.
+        assert_eq!(dry.len(), 9);
+        let &[(first, len)] = dry.corridors(dry.start) else {
+            panic!("The start should lead to one junction");
+        };
+        assert_eq!((dry.point(first), len), (Point::new(3, 5), 15));
+        assert_eq!(icy.longest_path(false), Some(94));
+        assert_eq!(icy.longest_path(true), Some(94));
+        assert_eq!(dry.longest_path(false), Some(154));
+        assert_eq!(dry.longest_path(true), Some(154));
     }
 }
@@ -1,98 +1,209 @@
-use std::fs;
+use crate::answer::Answer;
+use crate::math::{self, Rational};
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 
-const LOW: f64 = 200000000000000.;
-const HIGH: f64 = 400000000000000.;
+const LOW: i64 = 200000000000000;
+const HIGH: i64 = 400000000000000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
 
 pub struct Day24 {
-    lines: Vec<([f64; 3], [f64; 3])>
+    hailstones: Vec<Hailstone>
+}
+
+impl Hailstone {
+    /// Where on the x-y plane the paths of `self` and `other` cross, along with the times
+    /// each of them gets there. `None` when the paths are parallel.
+    pub fn crossing(&self, other: &Hailstone) -> Option<([Rational; 2], Rational, Rational)> {
+        let [t, s] = math::solve_2x2(
+            [[self.velocity[0], -other.velocity[0]], [self.velocity[1], -other.velocity[1]]],
+            [other.position[0] - self.position[0], other.position[1] - self.position[1]],
+        )?;
+        let at = |axis: usize| Rational::from(self.position[axis]) + t * Rational::from(self.velocity[axis]);
+        Some(([at(0), at(1)], t, s))
+    }
 }
 
 impl Day24 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day24")
-            .expect("Cannot read file")
-            .split('\n')
+        parse::load("data/year2023/day24", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let hailstones = input
+            .lines()
             .map(|line| {
-                let (p1, dp2) = line.split_once('@').expect("Line malformed");
-                let p1_nums = Day24::str_to_vec3(p1);
-                let dp2_nums = Day24::str_to_vec3(dp2);
-                ([p1_nums[0], p1_nums[1], p1_nums[2]], [dp2_nums[0], dp2_nums[1], dp2_nums[2]])
-            }).collect::<Vec<_>>();
-
-        Day24 {
-            lines
-        }
+                let (position, velocity) = parse::split_once(input, line, "@")?;
+                Ok(Hailstone {
+                    position: Day24::str_to_vec3(input, position)?,
+                    velocity: Day24::str_to_vec3(input, velocity)?,
+                })
+            }).collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(Day24 {
+            hailstones
+        })
     }
 
-    fn str_to_vec3(point: &str) -> Vec<f64> {
+    fn str_to_vec3(input: &str, point: &str) -> Result<[i64; 3], ParseError> {
         point.trim().split(',')
-            .map(|x| x.trim().parse::<f64>()
-                .unwrap_or_else(|err| panic!("Cannot parse {x};; Error: {err}"))
-            ).collect::<Vec<_>>()
+            .map(|x| parse::number(input, x.trim()))
+            .collect::<Result<Vec<_>, _>>()?
+            .try_into()
+            .map_err(|_| ParseError::at(input, point, "three comma separated numbers"))
     }
 
-    pub fn convert_to_2d_lines(&self) -> Vec<(f64, f64)> {
-    //     converting 2 points to y = mx + c
-        self.lines.iter().map(|(p1_nums, dp2_nums)| {
-            let m = dp2_nums[1] / dp2_nums[0];
-            let c = p1_nums[1] - m * p1_nums[0];
-            (m, c)
-        }).collect::<Vec<_>>()
+    /// How many pairs of hailstones have paths crossing inside the square from `low` to
+    /// `high` on the x-y plane, ignoring crossings in the past.
+    pub fn crossings(&self, low: i64, high: i64) -> u64 {
+        let (low, high) = (Rational::from(low), Rational::from(high));
+        let mut count = 0;
+        for (i, a) in self.hailstones.iter().enumerate() {
+            for b in &self.hailstones[i + 1..] {
+                let Some((point, t, s)) = a.crossing(b) else {
+                    continue;
+                };
+                if t >= Rational::ZERO && s >= Rational::ZERO
+                    && point.iter().all(|&v| low <= v && v <= high)
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
     }
 
-    pub fn intersection(l1: &(f64, f64), l2: &(f64, f64)) -> (f64, f64) {
-    //     line is in form y = mx + c
-        let (m1, c1) = l1;
-        let (m2, c2) = l2;
-        let x = (c2 - c1) / (m1 - m2);
-        let y = m1* x + c1;
-        (x, y)
+    /// Whether a rock thrown from `position` at `velocity` hits `hailstone` at some time
+    /// from now on. It does when the hailstone's position relative to the rock only ever
+    /// moves straight towards it.
+    fn hits(position: [i64; 3], velocity: [i64; 3], hailstone: &Hailstone) -> bool {
+        let offset = [0, 1, 2].map(|axis| (hailstone.position[axis] - position[axis]) as i128);
+        let closing = [0, 1, 2].map(|axis| (velocity[axis] - hailstone.velocity[axis]) as i128);
+        if closing == [0; 3] {
+            return offset == [0; 3];
+        }
+        cross(offset, closing) == [0; 3] && (0..3).map(|axis| offset[axis] * closing[axis]).sum::<i128>() >= 0
     }
 
-    pub fn magnitude(v: &(f64, f64)) -> f64 {
-        let (x, y) = v;
-        (x * x + y * y).sqrt()
+    /// The rock thrown from a whole number position at a whole number velocity that hits
+    /// every hailstone, as its starting position and velocity.
+    pub fn throw(&self) -> Option<([i64; 3], [i64; 3])> {
+        let (position, velocity) = self.hailstones.windows(3).find_map(|window| Day24::throw_at(&window[0], &window[1], &window[2]))?;
+        self.hailstones
+            .iter()
+            .all(|hailstone| Day24::hits(position, velocity, hailstone))
+            .then_some((position, velocity))
     }
 
-    pub fn cosine_2_vec(v1: &(f64, f64), v2: &(f64, f64)) -> f64 {
-        let (x1, y1) = v1;
-        let (x2, y2) = v2;
-        let dot = x1 * x2 + y1 * y2;
-        dot / (Day24::magnitude(v1) * Day24::magnitude(v2))
-    }
+    /// The rock that hits `a`, `b` and `c`, worked out in the frame of `a`.
+    ///
+    /// A rock at `p` moving at `v` meets hailstone `i` when `(p - p_i) × (v - v_i) = 0`. With
+    /// `a` still at the origin its equation is `p × v = 0`, and subtracting that from the
+    /// equation of `b` leaves the linear `p × v_b + p_b × v = p_b × v_b`. The rock's path runs through the origin and
+    /// meets the paths of `b` and `c`, so it lies in both planes they make with the origin,
+    /// which fixes its direction `d`. Then `p = m * d` and `v = k * d`, and the equation of `b`
+    /// leaves two unknowns. Solving for all six at once overflows the fractions instead.
+    fn throw_at(a: &Hailstone, b: &Hailstone, c: &Hailstone) -> Option<([i64; 3], [i64; 3])> {
+        let relative = |h: &Hailstone| {
+            let position = [0, 1, 2].map(|axis| (h.position[axis] - a.position[axis]) as i128);
+            let velocity = [0, 1, 2].map(|axis| (h.velocity[axis] - a.velocity[axis]) as i128);
+            (position, velocity)
+        };
+        let ((pb, vb), (pc, vc)) = (relative(b), relative(c));
+        let direction = cross(cross(pb, vb), cross(pc, vc));
+        let scale = direction.into_iter().fold(0, math::gcd);
+        if scale == 0 {
+            return None;
+        }
+        let d = direction.map(|n| n / scale);
 
-    pub fn check_point_is_future(line: &([f64; 3], [f64; 3]), point: &(f64, f64)) -> bool {
-        let v1 = (line.1[0], line.1[1]);
-        let v2 = (point.0 - line.0[0], point.1 - line.0[1]);
-        let cos = Day24::cosine_2_vec(&v1, &v2);
-        cos.round() == 1.
+        // m * (d × v_b) + k * (p_b × d) = p_b × v_b, whose sides all lie along the normal of
+        // the plane of `b`, so it only gives one equation. One from `c` as well does it.
+        let equations = |p: [i128; 3], v: [i128; 3]| {
+            let (by_m, by_k, rhs) = (cross(d, v), cross(p, d), cross(p, v));
+            [0, 1, 2].map(|axis| (by_m[axis], by_k[axis], rhs[axis]))
+        };
+        let (from_b, from_c) = (equations(pb, vb), equations(pc, vc));
+        let solution = from_b.iter().flat_map(|b| from_c.iter().map(move |c| [b, c])).find_map(|pair| {
+            let matrix = pair.map(|&(by_m, by_k, _)| vec![Rational::from(by_m), Rational::from(by_k)]).to_vec();
+            math::solve_linear(matrix, pair.map(|&(_, _, rhs)| Rational::from(rhs)).to_vec())
+        })?;
+        let (m, k) = (solution[0].to_integer()?, solution[1].to_integer()?);
+        let along = |start: [i64; 3], steps: i128| -> Option<[i64; 3]> {
+            let values: Vec<i64> = (0..3).map(|axis| (start[axis] as i128 + steps * d[axis]).try_into().ok()).collect::<Option<_>>()?;
+            values.try_into().ok()
+        };
+        Some((along(a.position, m)?, along(a.velocity, k)?))
     }
+}
 
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
 }
 
 impl Day for Day24 {
-    fn part_1(&mut self) -> u64 {
-        let lines = self.convert_to_2d_lines();
-        let mut count = 0;
-        for i in 0..lines.len() - 1 {
-            for j in i+1..lines.len() {
-                let (x, y) = Day24::intersection(&lines[i], &lines[j]);
-                let is_future_of_i = Day24::check_point_is_future(&self.lines[i], &(x, y));
-                let is_future_of_j = Day24::check_point_is_future(&self.lines[j], &(x, y));
-                if x >= LOW && y >= LOW && x <= HIGH && y <= HIGH
-                    && is_future_of_i
-                    && is_future_of_j
-                {
-                    count += 1;
-                }
-            }
-        }
-        count
+    fn part_1(&self) -> Answer {
+        self.crossings(LOW, HIGH).into()
+    }
+
+    fn part_2(&self) -> Answer {
+        let (position, _) = self.throw().expect("No rock throw hits every hailstone");
+        position.iter().sum::<i64>().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year2023::day24::Day24;
+
+    const SAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day24::parse(SAMPLE).unwrap();
+        assert_eq!(day.crossings(7, 27), 2);
+        assert_eq!(day.throw(), Some(([24, 13, 10], [-3, 1, 2])));
+    }
+
+    #[test]
+    fn test_large_throw() {
+        // Hailstones at puzzle-sized coordinates, all on the path of one rock.
+        let (rock, rock_velocity) = ([287430900705823_i64, 451620998712421, 260730677041648], [-47_i64, -248, 47]);
+        let input: String = (1..=6_i64)
+            .map(|i| {
+                let velocity = [17 * i - 60, 31 - 9 * i, 5 * i * i - 40];
+                let t = 93_000_000_000 * i + 7_777 * i * i;
+                let position = [0, 1, 2].map(|axis| rock[axis] + t * (rock_velocity[axis] - velocity[axis]));
+                format!("{}, {}, {} @ {}, {}, {}\n", position[0], position[1], position[2], velocity[0], velocity[1], velocity[2])
+            })
+            .collect();
+        let day = Day24::parse(&input).unwrap();
+        assert_eq!(day.throw(), Some((rock, rock_velocity)));
     }
 
-    fn part_2(&mut self) -> u64 {
-        todo!()
+    #[test]
+    fn test_fast_throw() {
+        // A rock far quicker than any hailstone, thrown at them from behind.
+        let (rock, rock_velocity) = ([-6_000_000_i64, 4_000_000, 10_000_000], [4_321_i64, -2_500, -3_999]);
+        let input: String = [(2, [3, -1, 7]), (5, [-4, 2, 1]), (9, [0, 5, -6]), (14, [8, 8, -2])]
+            .into_iter()
+            .map(|(t, velocity)| {
+                let position = [0, 1, 2].map(|axis| rock[axis] + t * (rock_velocity[axis] - velocity[axis]));
+                format!("{}, {}, {} @ {}, {}, {}\n", position[0], position[1], position[2], velocity[0], velocity[1], velocity[2])
+            })
+            .collect();
+        let day = Day24::parse(&input).unwrap();
+        assert_eq!(day.throw(), Some((rock, rock_velocity)));
     }
 }
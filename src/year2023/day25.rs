@@ -1,159 +1,112 @@
-use itertools::Itertools;
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    hash::{DefaultHasher, Hash, Hasher},
-};
-
+use std::sync::OnceLock;
+use crate::answer::Answer;
+use crate::graph::{Graph, Interner, MinCut};
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day25 {
-    graph: HashMap<u64, Vec<u64>>,
-    node_map: HashMap<u64, String>,
+    names: Interner,
+    graph: Graph,
+    /// Worked out on first use, so parsing stays cheap.
+    cut: OnceLock<MinCut>,
 }
 
 impl Day25 {
     pub fn new() -> Self {
-        let mut graph = HashMap::new();
-        let mut node_map = HashMap::new();
-
-        // Use this for testing with the sample input
-//         let input = "\
-// jqt: rhn xhk nvd
-// rsh: frs pzl lsr
-// xhk: hfx
-// cmg: qnr nvd lhk bvb
-// rhn: xhk bvb hfx
-// bvb: xhk hfx
-// pzl: lsr hfx nvd
-// qnr: nvd
-// ntq: jqt hfx bvb xhk
-// nvd: lhk
-// lsr: lhk
-// rzs: qnr cmg lsr rsh
-// frs: qnr lhk lsr";
-
-        //input
-        fs::read_to_string("data/year2023/day25")
-            .expect("Cannot read data file")
-            .split('\n')
-            .for_each(|line| {
-                let parts = line.split(':').collect::<Vec<&str>>();
-                let key = hash_string(parts[0].trim(), &mut node_map);
-                let vals = parts[1]
-                    .trim()
-                    .split_ascii_whitespace()
-                    .map(|s| hash_string(s, &mut node_map))
-                    .collect::<Vec<u64>>();
-                graph.insert(key, vals.clone());
-                for &child in &vals {
-                    graph.entry(child).or_insert_with(Vec::new).push(key);
-                }
-            });
-        
-        Day25 { graph, node_map }
+        parse::load("data/year2023/day25", Self::parse)
     }
 
-    fn connected_components(&self, graph: &HashMap<u64, Vec<u64>>) -> Vec<HashSet<u64>> {
-        let mut visited = HashSet::new();
-        let mut components = Vec::new();
-
-        for &node in graph.keys() {
-            if !visited.contains(&node) {
-                let mut component = HashSet::new();
-                self.dfs_collect(node, graph, &mut visited, &mut component);
-                components.push(component);
-            }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut names = Interner::new();
+        let mut edges = Vec::new();
+        for line in input.lines() {
+            let (key, vals) = parse::split_once(input, line, ":")?;
+            let key = names.intern(key.trim());
+            edges.extend(vals.split_ascii_whitespace().map(|val| (key, names.intern(val))));
+        }
+        if names.len() < 2 {
+            let at = input.lines().next().unwrap_or(&input[input.len()..]);
+            return Err(ParseError::at(input, at, "a wiring diagram with at least two nodes"));
         }
-        components
+        let graph = Graph::undirected(names.len(), edges);
+        Ok(Day25 { names, graph, cut: OnceLock::new() })
     }
 
-    fn dfs_collect(
-        &self,
-        node: u64,
-        graph: &HashMap<u64, Vec<u64>>,
-        visited: &mut HashSet<u64>,
-        component: &mut HashSet<u64>,
-    ) {
-        if visited.contains(&node) {
-            return;
-        }
-        visited.insert(node);
-        component.insert(node);
-        if let Some(neighbors) = graph.get(&node) {
-            for &neighbor in neighbors {
-                self.dfs_collect(neighbor, graph, visited, component);
-            }
-        }
+    /// The fewest wires to disconnect to split the components into two groups.
+    pub fn cut(&self) -> &MinCut {
+        self.cut.get_or_init(|| self.graph.min_cut().expect("Parsing checks for at least two nodes"))
+    }
+
+    /// The wires of the cut by name, each as `a/b` with the names in order, sorted.
+    pub fn wire_names(&self) -> Vec<String> {
+        let mut wires: Vec<String> = self
+            .cut()
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let (a, b) = (self.names.name(a), self.names.name(b));
+                format!("{}/{}", a.min(b), a.max(b))
+            })
+            .collect();
+        wires.sort();
+        wires
     }
 }
 
 impl Day for Day25 {
-    fn part_1(&mut self) -> u64 {
-        let mut edge_set = HashSet::new();
-        for (&node, neighbors) in &self.graph {
-            for &neighbor in neighbors {
-                let (a, b) = if node < neighbor {
-                    (node, neighbor)
-                } else {
-                    (neighbor, node)
-                };
-                edge_set.insert((a, b));
-            }
-        }
-        let edges: Vec<(u64, u64)> = edge_set.into_iter().collect();
+    fn part_1(&self) -> Answer {
+        let cut = self.cut();
+        (cut.side.len() as u64 * cut.rest as u64).into()
+    }
 
-        let combinations = edges.iter().combinations(3);
+    fn part_2(&self) -> Answer {
+        0u64.into()
+    }
+}
 
-        let result = combinations
-            .into_iter()
-            .map(|edge_combo| {
-                let mut test_graph = self.graph.clone();
-                for &&(u, v) in &edge_combo {
-                    if let Some(neighbors) = test_graph.get_mut(&u) {
-                        neighbors.retain(|&n| n != v);
-                    }
-                    if let Some(neighbors) = test_graph.get_mut(&v) {
-                        neighbors.retain(|&n| n != u);
-                    }
-                }
-                let components = self.connected_components(&test_graph);
-                if components.len() == 2 {
-                    let sizes = components.iter().map(|c| c.len()).collect::<Vec<_>>();
-                    let product = sizes[0] * sizes[1];
-                    Some((product, edge_combo.clone()))
-                } else {
-                    None
-                }
-            })
-            .filter_map(|x| x)
-            .max_by_key(|&(product, _)| product);
+#[cfg(test)]
+mod tests {
+    use crate::year2023::day25::Day25;
 
-        if let Some((max_product, best_combo)) = result {
-            println!("Maximum product: {}", max_product);
-            println!("Edges to disconnect:");
-            for &(u, v) in best_combo {
-                println!(
-                    "{} - {}",
-                    self.node_map.get(&u).unwrap(),
-                    self.node_map.get(&v).unwrap()
-                );
-            }
-            max_product as u64
-        } else {
-            0
-        }
+    const SAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day25::parse(SAMPLE).unwrap();
+        let cut = day.cut();
+        let mut sizes = [cut.side.len(), cut.rest];
+        sizes.sort();
+        assert_eq!(sizes, [6, 9]);
+        assert_eq!(day.wire_names(), vec!["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
     }
 
-    fn part_2(&mut self) -> u64 {
-        0
+    #[test]
+    fn test_repeated_lines() {
+        // A wire listed from both ends, and a component listed twice, is still one wire.
+        let day = Day25::parse(&format!("{SAMPLE}hfx: pzl\nxhk: ntq\n")).unwrap();
+        assert_eq!(day.wire_names(), vec!["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
     }
-}
 
-fn hash_string(s: &str, map: &mut HashMap<u64, String>) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    s.hash(&mut hasher);
-    let res = hasher.finish();
-    map.insert(res, s.to_string());
-    res
+    #[test]
+    fn test_parse_error() {
+        let err = Day25::parse("jqt:\n").err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (1, "jqt:"));
+        assert_eq!(err.expected, "a wiring diagram with at least two nodes");
+        let err = Day25::parse("").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, ""));
+        assert!(Day25::parse("jqt rhn\n").is_err());
+    }
 }
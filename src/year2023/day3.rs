@@ -1,141 +1,142 @@
-use std::fs;
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use regex::Regex;
 use rayon::prelude::*;
 
+const SCHEMATIC: &str = ".0123456789!\"#$%&'()*+,-/:;<=>?@[\\]^_`{|}~";
+
 pub struct Day3 {
-    lines: Vec<String>,
+    grid: Grid<char>,
     num_re: Regex,
     symbol_re: Regex
 }
 
+/// A part number spanning columns `start..=end` of row `y`.
+struct Number {
+    y: i32,
+    start: i32,
+    end: i32,
+    value: u64,
+}
+
+impl Number {
+    fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.start..=self.end).map(|x| Point::new(x, self.y))
+    }
+
+    fn is_adjacent(&self, pos: Point) -> bool {
+        (pos.y - self.y).abs() <= 1 && pos.x >= self.start - 1 && pos.x <= self.end + 1
+    }
+}
+
 impl Day3 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2023/day3")
-            .unwrap_or_else(|err| panic!("Cannot read input;; Err {err}"))
-            .split('\n')
-            .map(String::from)
-            .collect::<Vec<_>>();
-
-        Day3 {
-            lines,
-            num_re: Regex::new(r"\d+").unwrap(),
-            symbol_re: Regex::new(r"[^0-9.]").unwrap()
-        }
+        parse::load("data/year2023/day3", Self::parse)
     }
 
-    fn check_if_symbol_present(&self, x: usize, mut y1: usize, mut y2: usize) -> bool {
-        // Check same line
-        if y1 > 0 &&
-            self.symbol_re.is_match(&self.lines[x][y1-1..y1]) {
-            return true
-        }
-        if y2 < self.lines[x].len() - 1 &&
-            self.symbol_re.is_match(&self.lines[x][y2+1..y2+2]) {
-            return true
-        }
-        // Check line above
-        if y1 > 0 {
-            y1 = y1.saturating_sub(1);
-        }
-        if y2 < self.lines[x].len() - 1 {
-            y2 += 1;
-        }
-        if x > 0 && self.symbol_re.is_match(&self.lines[x-1][y1..y2+1]) {
-            return true
-        }
-        // Check line below
-        if x < self.lines.len() -1 &&
-            self.symbol_re.is_match(&self.lines[x+1][y1..y2+1]) {
-            return true
-        }
-        false
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Day3 {
+            grid: Grid::parse(input, input, SCHEMATIC)?,
+            num_re: Regex::new(r"\d+").unwrap(),
+            symbol_re: Regex::new(r"[^0-9.]").unwrap()
+        })
     }
 
-    fn find_adjacent_star(&self, x: usize, y: usize) -> Vec<u64> {
-        let mut adjacent = Vec::new();
-        if x > 0 {
-            for num in self.num_re.find_iter(&self.lines[x-1]) {
-                let y1 = num.start();
-                let y2 = num.end() - 1;
-                if !(y2 < y - 1 || y1 > y + 1) {
-                    adjacent.push(num.as_str().parse::<u64>().unwrap());
-                }
+    fn numbers(&self) -> Vec<Number> {
+        let mut numbers = Vec::new();
+        for (y, row) in self.grid.rows().enumerate() {
+            let row: String = row.iter().collect();
+            for num in self.num_re.find_iter(&row) {
+                numbers.push(Number {
+                    y: y as i32,
+                    start: num.start() as i32,
+                    end: num.end() as i32 - 1,
+                    value: num.as_str().parse().unwrap(),
+                });
             }
         }
+        numbers
+    }
 
-        for num in self.num_re.find_iter(&self.lines[x]) {
-            let y1 = num.start();
-            let y2 = num.end() - 1;
-            if y1 == y + 1 || y2 == y - 1 {
-                adjacent.push(num.as_str().parse::<u64>().unwrap());
-            }
-        }
+    fn is_symbol(&self, pos: Point) -> bool {
+        self.symbol_re.is_match(self.grid[pos].encode_utf8(&mut [0; 4]))
+    }
 
-        if x < self.lines.len() - 1 {
-            for num in self.num_re.find_iter(&self.lines[x+1]) {
-                let y1 = num.start();
-                let y2 = num.end() - 1;
-                if !(y2 < y - 1 || y1 > y + 1) {
-                    adjacent.push(num.as_str().parse::<u64>().unwrap());
-                }
-            }
-        }
+    fn check_if_symbol_present(&self, number: &Number) -> bool {
+        number
+            .cells()
+            .any(|cell| self.grid.neighbours8(cell).any(|p| self.is_symbol(p)))
+    }
 
-        adjacent
+    fn find_adjacent_star(numbers: &[Number], star: Point) -> Vec<u64> {
+        numbers
+            .iter()
+            .filter(|number| number.is_adjacent(star))
+            .map(|number| number.value)
+            .collect()
     }
 }
 
 impl Day for Day3 {
-    fn part_1(&mut self) -> u64 {
-        let res: u64 = self.lines.par_iter()
-            .enumerate()
-            .map(|(x, l)| {
-                self.num_re.find_iter(l)
-                    .filter_map(|n| {
-                        let num = n.as_str().parse::<u64>().unwrap();
-                        if self.check_if_symbol_present(x, n.start(), n.end() - 1) {
-                            Some(num)
-                        } else {
-                            None
-                        }
-                    }).sum::<u64>()
-            }).sum();
-        res
+    fn part_1(&self) -> Answer {
+        let res: u64 = self.numbers().par_iter()
+            .filter(|number| self.check_if_symbol_present(number))
+            .map(|number| number.value)
+            .sum();
+        res.into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let res: u64 = self.lines.par_iter()
-            .enumerate()
-            .map(|(x, line)| {
-                line.char_indices()
-                    .filter_map(|(y, c)| if c == '*' { Some(y) } else { None })
-                    .filter_map(|y| {
-                        let res = self.find_adjacent_star(x, y);
-                        if res.len() == 2 {
-                            Some(res[0] * res[1])
-                        } else {
-                            None
-                        }
-                    }).sum::<u64>()
+    fn part_2(&self) -> Answer {
+        let numbers = self.numbers();
+        let stars: Vec<Point> = self.grid.find_all(&'*').collect();
+        let res: u64 = stars.par_iter()
+            .filter_map(|&star| {
+                let res = Day3::find_adjacent_star(&numbers, star);
+                if res.len() == 2 {
+                    Some(res[0] * res[1])
+                } else {
+                    None
+                }
             }).sum();
-        res
+        res.into()
     }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use crate::traits::Day;
     use crate::year2023::day3::Day3;
 
+    const SAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
     #[test]
     fn test_regex() {
-        let day = Day3::new();
+        let day = Day3::parse(SAMPLE).unwrap();
         assert!(day.num_re.is_match("somethign 123 245 something"));
         assert!(day.symbol_re.is_match("...&-+"));
 
         for num in day.num_re.find_iter("somethign 123 245 something") {
-            println!("{}", num.as_str());
+            eprintln!("{}", num.as_str());
         }
     }
+
+    #[test]
+    fn test_sample() {
+        let day = Day3::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 4361u64.into());
+        assert_eq!(day.part_2(), 467835u64.into());
+    }
 }
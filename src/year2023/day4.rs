@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use rayon::prelude::*;
 
@@ -9,43 +10,38 @@ pub struct Day4 {
 
 impl Day4 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2023/day4")
-            .unwrap_or_else(|err| panic!("Error reading file;;Err {}", err));
+        parse::load("data/year2023/day4", Self::parse)
+    }
 
-        let lines:Vec<_> = data
-            .split('\n')
-            .map(|line| line.split(':')
-                .skip(1)
-                .collect::<String>()
-            ).collect();
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
 
-        let lines: Vec<_> = lines.par_iter()
-            .map(|x| x.trim().split('|')
-                .take(2)
-                .map(String::from)
-                .collect::<Vec<_>>())
+        let lines = input
+            .lines()
+            .collect::<Vec<_>>()
+            .par_iter()
             .map(|line| {
-            assert_eq!(line.len(), 2, "Expected line {:?} to have 2 parts", &line);
-            let nums = Day4::line_to_hashset(&line[0]);
-            let winnings = Day4::line_to_hashset(&line[1]);
-            winnings.intersection(&nums).count() as u64
-        }).collect();
+                let (_, numbers) = parse::split_once(input, line, ":")?;
+                let (nums, winnings) = parse::split_once(input, numbers, "|")?;
+                let nums = Day4::line_to_hashset(input, nums)?;
+                let winnings = Day4::line_to_hashset(input, winnings)?;
+                Ok(winnings.intersection(&nums).count() as u64)
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
 
-        Day4 {
+        Ok(Day4 {
             lines
-        }
+        })
     }
 
-    fn line_to_hashset(line: &String) -> HashSet<i32> {
+    fn line_to_hashset(input: &str, line: &str) -> Result<HashSet<i32>, ParseError> {
         line.split_whitespace()
-            .map(|x| x.parse::<i32>()
-                .unwrap_or_else(|err| panic!("Malformed value {x};; Err {err}")))
-            .collect::<HashSet<_>>()
+            .map(|x| parse::number(input, x))
+            .collect()
     }
 }
 
 impl Day for Day4 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.lines
             .iter()
             .map(|&overlap| {
@@ -54,10 +50,10 @@ impl Day for Day4 {
                 } else {
                     0
                 }
-            }).sum()
+            }).sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let n = self.lines.len();
         let mut card_count: HashMap<_, _> = (0..n).map(|key| (key as u64, 1_u64)).collect();
         self.lines.iter().enumerate().for_each(|(i, &val)| {
@@ -69,6 +65,6 @@ impl Day for Day4 {
                 }
             }
         });
-        card_count.values().sum()
+        card_count.values().sum::<u64>().into()
     }
 }
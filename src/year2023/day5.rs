@@ -1,91 +1,164 @@
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::ranges::{Interval, IntervalSet, PiecewiseMap};
 use crate::traits::Day;
-use rayon::prelude::*;
-
-type SeedMap = (u64, u64, u64);
 
 pub struct Day5 {
-    seeds: Vec<u64>,
-    maps: Vec<Vec<SeedMap>>
+    seeds: Vec<i64>,
+    maps: Vec<PiecewiseMap>
 }
 
 impl Day5 {
-    fn parse_line(line: &str) -> SeedMap {
-        let mut parts = line.split_whitespace()
-            .map(|x| x.parse::<u64>().unwrap_or_else(|err| panic!("Error parsing line {};; Error {}", x, err)));
-        let a = parts.next().unwrap_or_else(|| panic!("Dest map not present"));
-        let b = parts.next().unwrap_or_else(|| panic!("Source map not present"));
-        let c = parts.next().unwrap_or_else(|| panic!("Map range not present"));
-        (a, b, c)
+    /// Adds a `<destination> <source> <length>` line to `map`.
+    fn parse_line(input: &str, line: &str, map: &mut PiecewiseMap) -> Result<(), ParseError> {
+        let parts = line.split_whitespace()
+            .map(|x| parse::number(input, x))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+        let [dest, source, len] = parts[..] else {
+            return Err(ParseError::at(input, line, "`<destination> <source> <length>`"));
+        };
+        map.insert_range(dest, source, len);
+        Ok(())
     }
 
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2023/day5")
-            .unwrap_or_else(|err| panic!("Error reading file {}", err));
-        let mut parts = data.split("\n\n");
-        let seeds: Vec<u64> = parts.next()
-            .unwrap_or_else(|| panic!("Seed data not available"))
-            .split_whitespace()
-            .skip(1)
-            .map(|x| x.parse::<u64>()
-                .unwrap_or_else(|err| panic!("Cannot parse input {};; Err {}", x, err)))
-            .collect();
+        parse::load("data/year2023/day5", Self::parse)
+    }
 
-        let maps: Vec<_> = parts.map(|x| x.split('\n')
-            .skip(1)
-            .map(Day5::parse_line)
-            .collect()
-        ).collect();
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parts = input.split("\n\n");
+        let seed_line = parts.next().unwrap_or_default();
+        let (_, seeds) = parse::split_once(input, seed_line, "seeds:")?;
+        let seeds = seeds.split_whitespace()
+            .map(|x| parse::number(input, x))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+        // Part 2 reads the seeds as ranges, so they must pair up.
+        if seeds.len() % 2 != 0 {
+            return Err(ParseError::at(input, seed_line, "seeds in `<start> <length>` pairs"));
+        }
 
-        Day5 {
+        let maps = parts.map(|x| {
+            let mut map = PiecewiseMap::new();
+            for line in x.lines().skip(1) {
+                Day5::parse_line(input, line, &mut map)?;
+            }
+            Ok(map)
+        }).collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(Day5 {
             seeds,
             maps
-        }
+        })
     }
 
-    fn apply_map(&self, mut seed: u64) -> u64 {
-        for m in &self.maps {
-            for (dest, source, range) in m {
-                if *source <= seed && seed < source + range {
-                    seed = seed - source + dest;
-                    break;
-                }
-            }
-        }
-        seed
+    fn apply_map(&self, seed: i64) -> i64 {
+        self.maps.iter().fold(seed, |seed, map| map.apply(seed))
     }
 
-    fn apply_range_brute_force(&self, start: u64, range: u64) -> u64{
-        let mut min = u64::MAX;
-        for i in 0..range {
-            let r = self.apply_map(i + start);
-            if r < min {
-                min = r;
-            }
-        }
-        min
+    /// Pushes every seed of `seeds` through all the maps at once, range by range.
+    fn apply_ranges(&self, seeds: IntervalSet) -> IntervalSet {
+        self.maps.iter().fold(seeds, |seeds, map| map.apply_set(&seeds))
     }
-
 }
 
 impl Day for Day5 {
-    fn part_1(&mut self) -> u64 {
-        let res = self.seeds.par_iter().map(|x| self.apply_map(*x)).min();
-        res.unwrap_or(0)
+    fn part_1(&self) -> Answer {
+        let res = self.seeds.iter().map(|x| self.apply_map(*x)).min();
+        (res.unwrap_or(0) as u64).into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        println!("Go grab a coffee. We are going to be here for a while. 😅");
-        println!("Your CPU goes brrrrr....");
-        let seeds:Vec<_> = self.seeds.chunks(2)
-            .map(|chunk| (chunk[0], chunk[1]))
+    fn part_2(&self) -> Answer {
+        let seeds = self.seeds.chunks(2)
+            .map(|chunk| Interval::with_len(chunk[0], chunk[1]))
             .collect();
 
-        let res = seeds.par_iter()
-            .map(|(l, r)| self.apply_range_brute_force(*l, *r))
-            .min();
-
-        res.unwrap_or(0)
+        (self.apply_ranges(seeds).min().unwrap_or(0) as u64).into()
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::traits::Day;
+    use crate::year2023::day5::Day5;
+
+    const SAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    /// An almanac of small numbers, with mapping lines free to overlap.
+    fn generate(rng: &mut StdRng) -> String {
+        let seeds: Vec<String> = (0..4)
+            .map(|_| format!("{} {}", rng.gen_range(0..200), rng.gen_range(1..40)))
+            .collect();
+        let mut input = format!("seeds: {}", seeds.join(" "));
+        for stage in 0..7 {
+            input.push_str(&format!("\n\nstage-{stage} map:"));
+            for _ in 0..rng.gen_range(1..6) {
+                let line = (rng.gen_range(0..250), rng.gen_range(0..250), rng.gen_range(1..60));
+                input.push_str(&format!("\n{} {} {}", line.0, line.1, line.2));
+            }
+        }
+        input
+    }
+
+    #[test]
+    fn test_sample() {
+        let day = Day5::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 35u64.into());
+        assert_eq!(day.part_2(), 46u64.into());
+    }
+
+    #[test]
+    fn test_odd_seeds() {
+        let input = SAMPLE.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+        assert!(Day5::parse(&input).is_err());
+    }
+
+    #[test]
+    fn test_ranges_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..200 {
+            let input = generate(&mut rng);
+            let day = Day5::parse(&input).unwrap();
+            let brute_force = day.seeds.chunks(2)
+                .flat_map(|chunk| chunk[0]..chunk[0] + chunk[1])
+                .map(|seed| day.apply_map(seed))
+                .min()
+                .unwrap();
+            assert_eq!(day.part_2(), (brute_force as u64).into(), "{input}");
+        }
+    }
+}
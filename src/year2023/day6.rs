@@ -1,5 +1,6 @@
-use std::fs;
 use std::iter::zip;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day6 {
@@ -9,36 +10,30 @@ pub struct Day6 {
 
 impl Day6 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2023/day6")
-            .unwrap_or_else(|x| panic!("Empty input provided: {}", x));
-        let lines:Vec<&str> = data.split('\n').collect();
-        if lines.len() < 2 {
-            panic!("Input file does not contain 2 lines");
-        }
-        let mut parts = lines[0].split(':');
-        parts.next();
-        let time_str = parts.next()
-            .unwrap_or_else(|| panic!("Line 1 does not contain times"))
-            .trim()
-            .to_string();
+        parse::load("data/year2023/day6", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let time_line = parse::expect(input, lines.next(), input, "a line of times")?;
+        let distance_line = parse::expect(input, lines.next(), &input[input.len()..], "a line of distances")?;
 
-        let mut parts = lines[1].split(':');
-        parts.next();
-        let distance_str = parts.next()
-            .unwrap_or_else(|| panic!("Line 2 does not contain times"))
-            .trim()
-            .to_string();
+        let (_, time_str) = parse::split_once(input, time_line, ":")?;
+        let time_str = time_str.trim().to_string();
 
-        Day6 {
+        let (_, distance_str) = parse::split_once(input, distance_line, ":")?;
+        let distance_str = distance_str.trim().to_string();
+
+        Ok(Day6 {
             time_str,
             distance_str
-        }
+        })
     }
 }
 
 
 impl Day for Day6 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let time_num: Vec<i32> = self.time_str
             .split_whitespace()
             .map(|x| x.parse::<i32>()
@@ -61,10 +56,10 @@ impl Day for Day6 {
             res *= count;
         }
 
-        res
+        res.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let time_str = self.time_str.split_whitespace().collect::<String>();
         let distance_str = self.distance_str.split_whitespace().collect::<String>();
         let time_num = time_str.parse::<u64>()
@@ -79,6 +74,6 @@ impl Day for Day6 {
             }
         }
 
-        res
+        res.into()
     }
 }
@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
-use std::fs;
 use std::collections::HashMap;
 use std::iter::zip;
 use lazy_static::lazy_static;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 
@@ -59,10 +60,8 @@ impl Hand {
 
     }
 
-    fn new(line: &str, joker: bool) -> Hand {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        assert_eq!(parts.len(), 2, "Each line should have 2 part");
-        let mut freq_map = Hand::get_frequencies(parts[0]);
+    fn new(hand: &str, bid: i32, joker: bool) -> Hand {
+        let mut freq_map = Hand::get_frequencies(hand);
         if joker {
             if let Some(&joker_val) = freq_map.get(&'J') {
                 if let Some(k) = Hand::largest_key(&freq_map) {
@@ -81,8 +80,8 @@ impl Hand {
             f.push(5);
         }
         Hand {
-            hand: parts[0].to_string(),
-            bid: parts[1].parse::<i32>().unwrap_or(0),
+            hand: hand.to_string(),
+            bid,
             frequencies: f,
             joker
         }
@@ -155,56 +154,65 @@ impl Ord for Hand {
 
 
 pub struct Day7 {
-    input: String
+    hands: Vec<(String, i32)>
 }
 
 impl Day7 {
     pub fn new() -> Self {
-        Day7 {
-            input: Day7::get_input()
-        }
+        parse::load("data/year2023/day7", Self::parse)
     }
 
-    fn get_input() -> String {
-        let data_path = "data/year2023/day7";
-        fs::read_to_string(data_path).unwrap_or_default()
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let hands = input
+            .lines()
+            .map(|line| {
+                let (hand, bid) = parse::split_once(input, line, " ")?;
+                if hand.len() != 5 || !hand.chars().all(|c| CARD_MAPPING.contains_key(&c)) {
+                    return Err(ParseError::at(input, hand, "five cards out of `23456789TJQKA`"));
+                }
+                Ok((hand.to_string(), parse::number(input, bid.trim())?))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Day7 {
+            hands
+        })
     }
 
     fn parse_input(&self, joker:bool) -> Vec<Hand>{
-        let mut data: Vec<Hand> = self.input
-            .split('\n')
-            .map(|x| Hand::new(x, joker)).collect();
+        let mut data: Vec<Hand> = self.hands
+            .iter()
+            .map(|(hand, bid)| Hand::new(hand, *bid, joker)).collect();
 
-        assert_eq!(data.len(), 1000, "Input should have 1000 lines");
         data.sort();
         data
     }
 }
 
 impl Day for Day7 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut sum: u64 = 0;
         let data = self.parse_input(false);
         for (i, s) in data.iter().enumerate() {
             sum += (i as u64 + 1) * s.bid as u64;
         }
-        sum
+        sum.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut sum: u64 = 0;
         let data = self.parse_input(true);
         for (i, s) in data.iter().enumerate() {
             sum += (i as u64 + 1) * s.bid as u64;
         }
-        sum
+        sum.into()
     }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::year2023::day7::Hand;
+    use crate::year2023::day7::{Day7, Hand};
 
     #[test]
     fn test_largest_key() {
@@ -213,5 +221,14 @@ mod tests {
         let largest_key = Hand::largest_key(&freq_map).unwrap_or(&' ');
         assert_eq!(*largest_key, 'U');
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day7::parse("32T3K 765\nT55X5 684\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "T55X5"));
+
+        let err = Day7::parse("32T3K 765\nKK677 x\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 7, "a number"));
+    }
 }
 
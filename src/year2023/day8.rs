@@ -1,5 +1,7 @@
 use std::collections::HashMap;
-use std::fs;
+use crate::answer::Answer;
+use crate::math::lcm_all;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 type Node = [u8; 3];
@@ -11,46 +13,47 @@ pub struct Day8 {
 }
 
 impl Day8 {
-    fn str_to_u8_array(s: &str) -> Option<Node> {
-        let bytes = s.as_bytes();
-        if bytes.len() != 3 {
-            None
-        } else {
-            Some([bytes[0], bytes[1], bytes[2]])
+    fn str_to_u8_array(input: &str, s: &str) -> Result<Node, ParseError> {
+        match *s.as_bytes() {
+            [a, b, c] => Ok([a, b, c]),
+            _ => Err(ParseError::at(input, s, "a three letter node")),
         }
     }
-    pub fn new() -> Day8 {
-        let data = fs::read_to_string("data/year2023/day8").unwrap_or_default();
-        assert!(!data.is_empty(), "Input file is empty");
-        let mut parts = data.split_terminator('\n');
-        let moves: Vec<bool> = parts.next().unwrap_or_else(|| panic!("Insufficient data"))
-            .chars().map(|x| x == 'R').collect();
+    pub fn new() -> Self {
+        parse::load("data/year2023/day8", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parts = input.lines();
+        let move_line = parse::expect(input, parts.next(), input, "a line of moves")?;
+        if let Some((i, c)) = move_line.char_indices().find(|&(_, c)| c != 'L' && c != 'R') {
+            return Err(ParseError::at(input, &move_line[i..i + c.len_utf8()], "`L` or `R`"));
+        }
+        let moves: Vec<bool> = move_line.chars().map(|x| x == 'R').collect();
         parts.next();
         let mut map = HashMap::new();
         for line in parts {
-            let mut line_parts = line.split(" = ");
-            let key = Day8::str_to_u8_array(line_parts.next()
-                .unwrap_or_else(|| panic!("Line part malformed")))
-                .unwrap_or_else(|| panic!("Key does not contain 3 chars"));
-            let val = line_parts.next()
-                .unwrap_or_else(|| panic!("Line part malformed"));
-            let left = Day8::str_to_u8_array(&val[1..4])
-                .unwrap_or_else(|| panic!("Map val left does not contain 3 chars"));
-            let right = Day8::str_to_u8_array(&val[6..9])
-                .unwrap_or_else(|| panic!("Map val right does not contain 3 chars"));
+            let (key, val) = parse::split_once(input, line, " = ")?;
+            let key = Day8::str_to_u8_array(input, key)?;
+            let pair = val.strip_prefix('(')
+                .and_then(|v| v.strip_suffix(')'))
+                .ok_or_else(|| ParseError::at(input, val, "`(<left>, <right>)`"))?;
+            let (left, right) = parse::split_once(input, pair, ", ")?;
+            let left = Day8::str_to_u8_array(input, left)?;
+            let right = Day8::str_to_u8_array(input, right)?;
             map.insert(key, (left, right));
         }
 
-        Day8 {
+        Ok(Day8 {
             n: moves.len(),
             moves,
             map
-        }
+        })
     }
 
 
 
-    fn distance_till_z<'a>(&'a self, mut start: &'a Node) -> i32{
+    fn distance_till_z<'a>(&'a self, mut start: &'a Node) -> u64 {
         let mut count = 0;
         while start[2] != b'Z' {
             let (l, r) = &self.map[start];
@@ -61,31 +64,12 @@ impl Day8 {
             }
             count += 1;
         }
-        count as i32
-    }
-
-    pub fn gcd(mut a: u64, mut b: u64) -> u64 {
-        while b != 0 {
-            let temp = b;
-            b = a % b;
-            a = temp;
-        }
-        a
-    }
-
-    pub fn lcm(arr: Vec<i32>) -> u64 {
-        let mut res: u64 = 1;
-        for i in arr {
-            res = (res * i as u64) / Day8::gcd(res, i as u64);
-        }
-        res
+        count as u64
     }
-
-
 }
 
 impl Day for Day8 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut start = b"AAA";
         let mut count = 0;
         while start != b"ZZZ" {
@@ -98,11 +82,11 @@ impl Day for Day8 {
 
             count += 1;
         }
-        count as u64
+        count.into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let distances:Vec<i32> = self.map.keys()
+    fn part_2(&self) -> Answer {
+        let distances:Vec<u64> = self.map.keys()
             .filter_map(|x| {
                 if x[2] == b'A' {
                     Some(self.distance_till_z(x))
@@ -111,6 +95,6 @@ impl Day for Day8 {
                 }
             })
             .collect();
-        Day8::lcm(distances)
+        lcm_all(distances).into()
     }
 }
@@ -1,4 +1,5 @@
-use std::fs;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use rayon::prelude::*;
 
@@ -8,19 +9,21 @@ pub struct Day9 {
 
 impl Day9 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2023/day9")
-            .unwrap_or_else(|err| panic!("Error reading file {}", err));
+        parse::load("data/year2023/day9", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
         let mut sequences = Vec::new();
-        for d in data.split('\n') {
+        for d in input.lines() {
             let sequence: Vec<i32> = d.split_whitespace()
-                .map(|x| x.parse::<i32>().unwrap_or_else(|err| panic!("Cannot parse input, malformed value {};; err {}", x, err)))
-                .collect();
+                .map(|x| parse::number(input, x))
+                .collect::<Result<_, _>>()?;
             sequences.push(sequence);
         }
 
-        Day9 {
+        Ok(Day9 {
             sequences
-        }
+        })
     }
 
     fn get_sequence_last(seq: &Vec<i32>) -> i32 {
@@ -59,17 +62,17 @@ impl Day9 {
 }
 
 impl Day for Day9 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let res: i32 = self.sequences.par_iter()
             .map(Day9::get_sequence_last)
             .sum();
-        res as u64
+        res.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let res: i32 = self.sequences.par_iter()
             .map(Day9::get_sequence_first)
             .sum();
-        res as u64
+        res.into()
     }
 }
@@ -1,5 +1,7 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 pub struct Day1 {
     list1: Vec<u64>,
@@ -8,33 +10,38 @@ pub struct Day1 {
 
 impl Day1 {
     pub fn new() -> Self {
-        let (mut list1, mut list2): (Vec<u64>, Vec<u64>) = fs::read_to_string("data/year2024/day1")
-            .expect("Cannot read data")
+        parse::load("data/year2024/day1", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let (mut list1, mut list2): (Vec<u64>, Vec<u64>) = input
             .lines()
-            .filter_map(|line| {
+            .map(|line| {
                 let mut parts = line.split_ascii_whitespace();
-                let n1: u64 = parts.next()?.parse().ok()?;
-                let n2: u64 = parts.next()?.parse().ok()?;
-                Some((n1, n2))
+                let n1 = parse::expect(input, parts.next(), line, "two numbers")?;
+                let n2 = parse::expect(input, parts.next(), line, "two numbers")?;
+                Ok((parse::number(input, n1)?, parse::number(input, n2)?))
             })
+            .collect::<Result<Vec<(u64, u64)>, ParseError>>()?
+            .into_iter()
             .unzip();
 
         list1.sort();
         list2.sort();
-        Day1 { list1, list2 }
+        Ok(Day1 { list1, list2 })
     }
 }
 
 impl Day for Day1 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.list1
             .iter()
             .zip(self.list2.iter())
             .map(|(n1, n2)| n1.abs_diff(*n2))
-            .sum()
+            .sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut freq_count: HashMap<u64, u64> = HashMap::new();
         for n in &self.list2 {
             *freq_count.entry(*n).or_insert(0) += 1;
@@ -43,6 +50,6 @@ impl Day for Day1 {
         self.list1
             .iter()
             .map(|n| n * freq_count.get(n).unwrap_or(&0))
-            .sum()
+            .sum::<u64>().into()
     }
 }
@@ -1,5 +1,7 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day11 {
@@ -8,13 +10,16 @@ pub struct Day11 {
 
 impl Day11 {
     pub fn new() -> Self {
-        let numbers: Vec<u64> = fs::read_to_string("data/year2024/day11")
-            .expect("cannot read data file")
+        parse::load("data/year2024/day11", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers: Vec<u64> = input
             .split_ascii_whitespace()
-            .map(|p| p.parse().unwrap())
-            .collect();
+            .map(|p| parse::number(input, p))
+            .collect::<Result<_, _>>()?;
 
-        Day11 { numbers }
+        Ok(Day11 { numbers })
     }
 }
 
@@ -86,7 +91,7 @@ fn count_res(start: u64, step: u8, cache: &mut HashMap<(u64, u8), u64>) -> u64 {
 
 
 impl Day for Day11 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut start = self.numbers.clone();
         let mut freq_map = HashMap::new();
         for s in start.iter() {
@@ -99,13 +104,13 @@ impl Day for Day11 {
             }
         }
 
-        start.len() as u64
+        start.len().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut cache: HashMap<(u64, u8), u64> = HashMap::new();
         self.numbers.iter().map(|n| {
             count_res(*n, 75, &mut cache)
-        }).sum()
+        }).sum::<u64>().into()
     }
 }
@@ -1,216 +1,156 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs, usize,
-};
+use std::collections::{HashMap, HashSet};
 
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day12 {
-    graph: Vec<Vec<char>>,
+    graph: Grid<char>,
 }
 
 impl Day12 {
     pub fn new() -> Self {
-        let graph = fs::read_to_string("data/year2024/day12")
-            .expect("data not found")
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect();
-
-        Day12 { graph }
+        parse::load("data/year2024/day12", Self::parse)
     }
-}
 
-fn calculate_perimeter(graph: &Vec<Vec<char>>, x: usize, y: usize) -> u64 {
-    let ch = graph[y][x];
-    let x_lim = graph[0].len() - 1;
-    let y_lim = graph.len() - 1;
-    let mut perimeter = 0;
-    // west
-    if x == 0 || graph[y][x - 1] != ch {
-        perimeter += 1;
-    }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let graph = Grid::parse(input, input, "ABCDEFGHIJKLMNOPQRSTUVWXYZ")?;
 
-    // east
-    if x == x_lim || graph[y][x + 1] != ch {
-        perimeter += 1;
+        Ok(Day12 { graph })
     }
+}
 
-    // north
-    if y == 0 || graph[y - 1][x] != ch {
-        perimeter += 1;
-    }
+/// Sides of a plot, with the offset to the plot across them.
+const SIDES: [(i32, i32, char); 4] = [(-1, 0, 'W'), (1, 0, 'E'), (0, -1, 'N'), (0, 1, 'S')];
 
-    // south
-    if y == y_lim || graph[y + 1][x] != ch {
-        perimeter += 1;
-    }
+fn same_region(graph: &Grid<char>, pos: Point, dx: i32, dy: i32) -> bool {
+    graph.get(pos.offset(dx, dy)) == Some(&graph[pos])
+}
 
-    perimeter
+fn calculate_perimeter(graph: &Grid<char>, pos: Point) -> u64 {
+    SIDES
+        .iter()
+        .filter(|&&(dx, dy, _)| !same_region(graph, pos, dx, dy))
+        .count() as u64
 }
 
 fn traverse(
-    graph: &Vec<Vec<char>>,
-    x: usize,
-    y: usize,
+    graph: &Grid<char>,
+    pos: Point,
     area: &mut u64,
     perimeter: &mut u64,
-    visited: &mut HashSet<(usize, usize)>,
+    visited: &mut HashSet<Point>,
 ) {
-    if visited.contains(&(x, y)) {
+    if visited.contains(&pos) {
         return;
     }
-    visited.insert((x, y));
-    *perimeter += calculate_perimeter(graph, x, y);
+    visited.insert(pos);
+    *perimeter += calculate_perimeter(graph, pos);
     *area += 1;
-    let ch = graph[y][x];
+    let ch = graph[pos];
 
-    // west
-    if x > 0 && graph[y][x - 1] == ch {
-        traverse(graph, x - 1, y, area, perimeter, visited);
-    }
-    // east
-    if x < graph[0].len() - 1 && graph[y][x + 1] == ch {
-        traverse(graph, x + 1, y, area, perimeter, visited);
-    }
-    // north
-    if y > 0 && graph[y - 1][x] == ch {
-        traverse(graph, x, y - 1, area, perimeter, visited);
-    }
-    // south
-    if y < graph.len() - 1 && graph[y + 1][x] == ch {
-        traverse(graph, x, y + 1, area, perimeter, visited);
+    for next in graph.neighbours4(pos) {
+        if graph[next] == ch {
+            traverse(graph, next, area, perimeter, visited);
+        }
     }
 }
 
 fn traverse_2(
-    graph: &Vec<Vec<char>>,
-    x: usize,
-    y: usize,
+    graph: &Grid<char>,
+    pos: Point,
     area: &mut u64,
     side: &mut HashSet<(i32, i32, char)>,
-    visited: &mut HashSet<(usize, usize)>,
+    visited: &mut HashSet<Point>,
 ) {
-    if visited.contains(&(x, y)) {
+    if visited.contains(&pos) {
         return;
     }
-    visited.insert((x, y));
+    visited.insert(pos);
 
     *area += 1;
-    let ch = graph[y][x];
-
-    let x_lim = graph[0].len() - 1;
-    let y_lim = graph.len() - 1;
-
-    let xi = x as i32;
-    let yi = y as i32;
-    // west
-    if xi == 0 || graph[y][x - 1] != ch {
-        side.insert((xi - 1, yi, 'W'));
-    }
+    let ch = graph[pos];
 
-    // east
-    if x == x_lim || graph[y][x + 1] != ch {
-        side.insert((xi + 1, yi, 'E'));
-    }
-
-    // north
-    if y == 0 || graph[y - 1][x] != ch {
-        side.insert((xi, yi - 1, 'N'));
-    }
-
-    // south
-    if y == y_lim || graph[y + 1][x] != ch {
-        side.insert((xi, yi + 1, 'S'));
+    for (dx, dy, dir) in SIDES {
+        if !same_region(graph, pos, dx, dy) {
+            side.insert((pos.x + dx, pos.y + dy, dir));
+        }
     }
 
-    // west
-    if x > 0 && graph[y][x - 1] == ch {
-        traverse_2(graph, x - 1, y, area, side, visited);
-    }
-    // east
-    if x < graph[0].len() - 1 && graph[y][x + 1] == ch {
-        traverse_2(graph, x + 1, y, area, side, visited);
-    }
-    // north
-    if y > 0 && graph[y - 1][x] == ch {
-        traverse_2(graph, x, y - 1, area, side, visited);
-    }
-    // south
-    if y < graph.len() - 1 && graph[y + 1][x] == ch {
-        traverse_2(graph, x, y + 1, area, side, visited);
+    for next in graph.neighbours4(pos) {
+        if graph[next] == ch {
+            traverse_2(graph, next, area, side, visited);
+        }
     }
 }
 
 impl Day for Day12 {
-    fn part_2(&mut self) -> u64 {
-        let mut visited_map: HashMap<char, HashSet<(usize, usize)>> = HashMap::new();
+    fn part_2(&self) -> Answer {
+        let mut visited_map: HashMap<char, HashSet<Point>> = HashMap::new();
 
         let mut total = 0;
-        for (y, line) in self.graph.iter().enumerate() {
-            for (x, ch) in line.iter().enumerate() {
-                let visited = visited_map.entry(*ch).or_insert(HashSet::new());
-                let mut area = 0;
-                let mut side = HashSet::new();
-                traverse_2(&self.graph, x, y, &mut area, &mut side, visited);
-                if area > 0 {
-                    let mut side = side.into_iter().collect::<Vec<(i32, i32, char)>>();
-                    side.sort_by(|a, b| {
-                        a.2.cmp(&b.2).then_with(|| match a.2 {
-                            'W' | 'E' => a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)),
-                            _ => a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)),
-                        })
-                    });
-
-                    // count edges
-                    let mut count = 0;
-                    let mut prev: Option<(i32, i32, char)> = None;
-                    side.iter()
-                        .filter(|a| a.2 == 'E' || a.2 == 'W')
-                        .for_each(|(x, y, dir)| {
-                            if let Some(prev) = prev {
-                                if prev.2 != *dir || prev.0 != *x || prev.1 != *y - 1 {
-                                    count += 1;
-                                }
-                            } else {
+        for (pos, ch) in self.graph.iter() {
+            let visited = visited_map.entry(*ch).or_insert(HashSet::new());
+            let mut area = 0;
+            let mut side = HashSet::new();
+            traverse_2(&self.graph, pos, &mut area, &mut side, visited);
+            if area > 0 {
+                let mut side = side.into_iter().collect::<Vec<(i32, i32, char)>>();
+                side.sort_by(|a, b| {
+                    a.2.cmp(&b.2).then_with(|| match a.2 {
+                        'W' | 'E' => a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)),
+                        _ => a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)),
+                    })
+                });
+
+                // count edges
+                let mut count = 0;
+                let mut prev: Option<(i32, i32, char)> = None;
+                side.iter()
+                    .filter(|a| a.2 == 'E' || a.2 == 'W')
+                    .for_each(|(x, y, dir)| {
+                        if let Some(prev) = prev {
+                            if prev.2 != *dir || prev.0 != *x || prev.1 != *y - 1 {
                                 count += 1;
                             }
-                            prev = Some((*x, *y, *dir));
-                        });
-                    prev = None;
-                    side.iter()
-                        .filter(|a| a.2 == 'N' || a.2 == 'S')
-                        .for_each(|(x, y, dir)| {
-                            if let Some(prev) = prev {
-                                if prev.2 != *dir || prev.1 != *y || prev.0 != *x - 1 {
-                                    count += 1;
-                                }
-                            } else {
+                        } else {
+                            count += 1;
+                        }
+                        prev = Some((*x, *y, *dir));
+                    });
+                prev = None;
+                side.iter()
+                    .filter(|a| a.2 == 'N' || a.2 == 'S')
+                    .for_each(|(x, y, dir)| {
+                        if let Some(prev) = prev {
+                            if prev.2 != *dir || prev.1 != *y || prev.0 != *x - 1 {
                                 count += 1;
                             }
-                            prev = Some((*x, *y, *dir));
-                        });
+                        } else {
+                            count += 1;
+                        }
+                        prev = Some((*x, *y, *dir));
+                    });
 
-                    total += area * count;
-                }
+                total += area * count;
             }
         }
-        total
+        total.into()
     }
 
-    fn part_1(&mut self) -> u64 {
-        let mut visited_map: HashMap<char, HashSet<(usize, usize)>> = HashMap::new();
+    fn part_1(&self) -> Answer {
+        let mut visited_map: HashMap<char, HashSet<Point>> = HashMap::new();
 
         let mut total = 0;
-        for (y, line) in self.graph.iter().enumerate() {
-            for (x, ch) in line.iter().enumerate() {
-                let visited = visited_map.entry(*ch).or_insert(HashSet::new());
-                let mut area = 0;
-                let mut perimeter = 0;
-                traverse(&self.graph, x, y, &mut area, &mut perimeter, visited);
-                total += area * perimeter;
-            }
+        for (pos, ch) in self.graph.iter() {
+            let visited = visited_map.entry(*ch).or_insert(HashSet::new());
+            let mut area = 0;
+            let mut perimeter = 0;
+            traverse(&self.graph, pos, &mut area, &mut perimeter, visited);
+            total += area * perimeter;
         }
-        total
+        total.into()
     }
 }
@@ -1,95 +1,66 @@
-use std::fs;
 
+use crate::answer::Answer;
+use crate::math::solve_2x2_integer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day13 {
     data: Vec<((u64, u64), (u64, u64), (u64, u64))>,
 }
 
-fn line_to_point(l: &str) -> (u64, u64) {
-    let coord = l.split(':').skip(1).next().unwrap().trim();
-    let mut parts = coord.split(", ");
-    let x = parts.next().unwrap();
-    let y = parts.next().unwrap();
+fn line_to_point(input: &str, l: &str) -> Result<(u64, u64), ParseError> {
+    let (_, coord) = parse::split_once(input, l, ":")?;
+    let (x, y) = parse::split_once(input, coord.trim(), ", ")?;
     let start = 2;
-    (x[start..].parse().unwrap(), y[start..].parse().unwrap())
+    let x = parse::expect(input, x.get(start..), x, "`X+<number>` or `X=<number>`")?;
+    let y = parse::expect(input, y.get(start..), y, "`Y+<number>` or `Y=<number>`")?;
+    Ok((parse::number(input, x)?, parse::number(input, y)?))
 }
 
-fn solve_linear_equation(a: (u64, u64), b: (u64, u64), res: (u64, u64)) -> Option<(f64, f64)> {
-    let (a0, a1) = a;
-    let (b0, b1) = b;
-    let (res0, res1) = res;
-
-    let a0 = a0 as f64;
-    let a1 = a1 as f64;
-    let b0 = b0 as f64;
-    let b1 = b1 as f64;
-    let res0 = res0 as f64;
-    let res1 = res1 as f64;
-
-    let det = a0 * b1 - a1 * b0;
-    if det == 0. {
-        return None;
+/// Tokens spent pressing A and B to reach the prize, if whole presses can reach it.
+fn tokens(a: (u64, u64), b: (u64, u64), prize: (u64, u64)) -> u64 {
+    let buttons = [[a.0 as i64, b.0 as i64], [a.1 as i64, b.1 as i64]];
+    match solve_2x2_integer(buttons, [prize.0 as i64, prize.1 as i64]) {
+        Some([a, b]) if a >= 0 && b >= 0 => 3 * a as u64 + b as u64,
+        _ => 0,
     }
-
-    let x = (res0 * b1 - res1 * b0) / det;
-    let y = (a0 * res1 - a1 * res0) / det;
-    Some((x, y))
 }
 
 impl Day13 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2024/day13")
-            .expect("cannot read data file")
+        parse::load("data/year2024/day13", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = input
             .split("\n\n")
             .map(|p| {
                 let mut l = p.lines();
-                let a = line_to_point(l.next().unwrap());
-                let b = line_to_point(l.next().unwrap());
-                let res = line_to_point(l.next().unwrap());
-                (a, b, res)
+                let a = line_to_point(input, parse::expect(input, l.next(), p, "a button A line")?)?;
+                let b = line_to_point(input, parse::expect(input, l.next(), p, "a button B line")?)?;
+                let res = line_to_point(input, parse::expect(input, l.next(), p, "a prize line")?)?;
+                Ok((a, b, res))
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
 
-        Day13 { data }
+        Ok(Day13 { data })
     }
 }
 
 impl Day for Day13 {
-    fn part_1(&mut self) -> u64 {
-        let res: f64 = self
-            .data
+    fn part_1(&self) -> Answer {
+        self.data
             .iter()
-            .map(|(a, b, res)| {
-                let (a, b) = solve_linear_equation(*a, *b, *res)
-                    .expect(&format!("No solutions for {a:?}, {b:?} {res:?}"));
-                if a.fract() == 0.0 && b.fract() == 0.0 {
-                    3f64 * a + b
-                } else {
-                    0.0
-                }
-            })
-            .sum();
-
-        res as u64
+            .map(|&(a, b, res)| tokens(a, b, res))
+            .sum::<u64>()
+            .into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let res: f64 = self
-            .data
+    fn part_2(&self) -> Answer {
+        self.data
             .iter()
-            .map(|(a, b, res)| {
-                let r = (res.0 + 10000000000000, res.1 + 10000000000000); 
-                let (a, b) = solve_linear_equation(*a, *b, r)
-                    .expect(&format!("No solutions for {a:?}, {b:?} {res:?}"));
-                if a.fract() == 0.0 && b.fract() == 0.0 {
-                    3f64 * a + b
-                } else {
-                    0.0
-                }
-            })
-            .sum();
-
-        res as u64
+            .map(|&(a, b, res)| tokens(a, b, (res.0 + 10000000000000, res.1 + 10000000000000)))
+            .sum::<u64>()
+            .into()
     }
 }
@@ -1,42 +1,55 @@
-use std::{collections::HashSet, fs};
+use std::collections::HashSet;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day14 {
+    robots: Vec<((i32, i32), (i32, i32))>,
+    lim: (i32, i32),
+}
+
+/// Positions and velocities of the robots as they move around the room.
+pub struct Robots {
     pub data: Vec<((i32, i32), (i32, i32))>,
     lim: (i32, i32),
 }
 
-fn parse_str(part: &str) -> (i32, i32) {
-    let mut parts = part[2..].split(',');
-    (
-        parts.next().unwrap().parse().unwrap(),
-        parts.next().unwrap().parse().unwrap(),
-    )
+fn parse_str(input: &str, part: &str) -> Result<(i32, i32), ParseError> {
+    let coord = parse::expect(input, part.get(2..), part, "`p=<x>,<y>` or `v=<x>,<y>`")?;
+    let (x, y) = parse::split_once(input, coord, ",")?;
+    Ok((parse::number(input, x)?, parse::number(input, y)?))
 }
 
 impl Day14 {
     pub fn new() -> Self {
-        Day14 {
-            data: Vec::new(),
-            lim: (100, 102),
-        }
+        parse::load("data/year2024/day14", Self::parse)
     }
 
-    pub fn load(&mut self) {
-        let data = fs::read_to_string("data/year2024/day14")
-            .expect("cannot read data file")
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let robots: Vec<_> = input
             .lines()
             .map(|l| {
-                let mut parts = l.split_ascii_whitespace();
-                let pos = parts.next().unwrap();
-                let vel = parts.next().unwrap();
-                (parse_str(pos), parse_str(vel))
+                let (pos, vel) = parse::split_once(input, l, " ")?;
+                Ok((parse_str(input, pos)?, parse_str(input, vel)?))
             })
-            .collect();
-        self.data = data;
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Day14 {
+            robots,
+            lim: (100, 102),
+        })
     }
 
+    /// The robots at their starting positions.
+    pub fn robots(&self) -> Robots {
+        Robots {
+            data: self.robots.clone(),
+            lim: self.lim,
+        }
+    }
+}
+
+impl Robots {
     pub fn step(&mut self) {
         for (pos, vel) in self.data.iter_mut() {
             pos.0 += vel.0;
@@ -87,10 +100,10 @@ impl Day14 {
 }
 
 impl Day for Day14 {
-    fn part_1(&mut self) -> u64 {
-        self.load();
+    fn part_1(&self) -> Answer {
+        let mut robots = self.robots();
         for _ in 0..100 {
-            self.step();
+            robots.step();
         }
 
         // quadrants
@@ -106,7 +119,7 @@ impl Day for Day14 {
 
         let mut total = 1;
         for (low, high) in quads {
-            let res = self
+            let res = robots
                 .data
                 .iter()
                 .filter(|((x, y), _)| *x >= low.0 && *y >= low.1 && *x <= high.0 && *y <= high.1)
@@ -115,28 +128,28 @@ impl Day for Day14 {
             total *= res;
         }
 
-        total as u64
+        total.into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        self.load();
+    fn part_2(&self) -> Answer {
+        let mut robots = self.robots();
         let mut count = 0;
-        while !self.is_tree() {
-            self.step();
+        while !robots.is_tree() {
+            robots.step();
             count += 1;
         }
         // print_out
         let mut grid = vec![vec!['.'; 101]; 103];
-        for (pos, _) in self.data.iter() {
+        for (pos, _) in robots.data.iter() {
             grid[pos.1 as usize][pos.0 as usize] = '*';
         }
         for y in 0..grid.len() {
             for x in 0..grid[0].len() {
-                print!("{}", grid[y][x]);
+                eprint!("{}", grid[y][x]);
             }
-            println!();
+            eprintln!();
         }
 
-        return count;
+        return count.into();
     }
 }
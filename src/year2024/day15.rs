@@ -1,128 +1,116 @@
-use std::fs;
-
+use crate::answer::Answer;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day15 {
-    pub grid: Vec<Vec<char>>,
-    pub movement: Vec<char>,
+    grid: Grid<char>,
+    pub movement: Vec<Direction>,
+}
+
+/// The warehouse as the robot pushes boxes around.
+pub struct Warehouse {
+    pub grid: Grid<char>,
+}
+
+/// Sum of the GPS coordinates of every `tile`.
+fn gps_total(grid: &Grid<char>, tile: char) -> usize {
+    grid.find_all(&tile).map(|pos| 100 * pos.y as usize + pos.x as usize).sum()
 }
 
 impl Day15 {
     pub fn new() -> Self {
-        Day15 {
-            grid: Vec::new(),
-            movement: Vec::new(),
-        }
+        parse::load("data/year2024/day15", Self::parse)
     }
 
-    pub fn load(&mut self) {
-        let data_str = fs::read_to_string("data/year2024/day15").expect("Cannot read data");
-        let mut parts = data_str.split("\n\n");
-        let grid: Vec<Vec<char>> = parts
-            .next()
-            .unwrap()
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect();
-        let movement = parts.next().unwrap().chars().collect();
-        self.grid = grid;
-        self.movement = movement;
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let (grid_str, movement_str) = parse::split_once(input, input, "\n\n")?;
+        let grid = Grid::parse(input, grid_str, "#.O@")?;
+        let movement = movement_str
+            .char_indices()
+            .filter(|&(_, c)| c != '\n')
+            .map(|(i, c)| match c {
+                '^' | 'v' | '<' | '>' => Ok(Direction::from_char(c).unwrap()),
+                _ => Err(ParseError::at(input, &movement_str[i..i + c.len_utf8()], "one of `^v<>`")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Day15 {
+            grid,
+            movement,
+        })
     }
 
-    pub fn modify_grid(&mut self) {
-        let mut new_grid = Vec::new();
-
-        for (y, line) in self.grid.iter().enumerate() {
-            new_grid.push(Vec::new());
-            for (_, ch) in line.iter().enumerate() {
-                match ch {
-                    '.' => {
-                        new_grid[y].push('.');
-                        new_grid[y].push('.')
-                    }
-                    '#' => {
-                        new_grid[y].push('#');
-                        new_grid[y].push('#')
-                    }
-                    'O' => {
-                        new_grid[y].push('[');
-                        new_grid[y].push(']')
-                    }
-                    _ => {
-                        new_grid[y].push('@');
-                        new_grid[y].push('.')
-                    }
-                }
-            }
+    /// The warehouse before the robot moves.
+    pub fn warehouse(&self) -> Warehouse {
+        Warehouse {
+            grid: self.grid.clone(),
         }
+    }
 
-        self.grid = new_grid;
+    /// The warehouse before the robot moves, with every tile twice as wide.
+    pub fn wide_warehouse(&self) -> Warehouse {
+        let rows = self.grid.rows().map(|line| {
+            line.iter()
+                .flat_map(|ch| match ch {
+                    '.' => ['.', '.'],
+                    '#' => ['#', '#'],
+                    'O' => ['[', ']'],
+                    _ => ['@', '.'],
+                })
+                .collect()
+        });
+
+        Warehouse { grid: Grid::from_rows(rows) }
     }
+}
 
+impl Warehouse {
     pub fn get(&self, x: i32, y: i32) -> char {
-        self.grid[y as usize][x as usize]
+        self.grid[Point::new(x, y)]
     }
 
-    pub fn get_start(grid: &Vec<Vec<char>>) -> Option<(usize, usize)> {
-        for (y, l) in grid.iter().enumerate() {
-            for (x, ch) in l.iter().enumerate() {
-                if *ch == '@' {
-                    return Some((x, y));
-                }
-            }
-        }
-        None
+    pub fn start(&self) -> Option<Point> {
+        self.grid.find(&'@')
     }
 
-    pub fn step(&mut self, dir: char, start: (usize, usize)) -> (usize, usize) {
-        let (dx, dy): (i32, i32) = match dir {
-            '^' => (0, -1),
-            '>' => (1, 0),
-            '<' => (-1, 0),
-            _ => (0, 1),
-        };
-
-        let is_vertical = dir == 'v' || dir == '^';
-
-        let (nxi, nyi) = (start.0 as i32 + dx, start.1 as i32 + dy);
-        let (nx, ny) = (nxi as usize, nyi as usize);
-        if self.grid[ny][nx] == '#' {
+    pub fn step(&mut self, dir: Direction, start: Point) -> Point {
+        let dy = dir.offset().y;
+        let next = start.step(dir);
+        if self.grid[next] == '#' {
             return start;
         }
 
-        if self.grid[ny][nx] == '.' {
-            self.grid[ny][nx] = '@';
-            self.grid[start.1][start.0] = '.';
-            return (nx, ny);
+        if self.grid[next] == '.' {
+            self.grid[next] = '@';
+            self.grid[start] = '.';
+            return next;
         }
 
-        if !is_vertical {
-            let (mut bxi, mut byi) = (nxi, nyi);
-            while self.grid[byi as usize][bxi as usize] == '['
-                || self.grid[byi as usize][bxi as usize] == ']'
-            {
-                bxi += dx;
-                byi += dy;
+        if dir.is_horizontal() {
+            let mut b = next;
+            while self.grid[b] == '[' || self.grid[b] == ']' {
+                b = b.step(dir);
             }
 
-            if self.grid[byi as usize][bxi as usize] == '#' {
+            if self.grid[b] == '#' {
                 return start;
             }
 
-            while self.grid[byi as usize][bxi as usize] != '@' {
-                let (pbxi, pbyi) = (bxi - dx, byi - dy);
-                self.grid[byi as usize][bxi as usize] = self.grid[pbyi as usize][pbxi as usize];
-
-                bxi -= dx;
-                byi -= dy;
+            while self.grid[b] != '@' {
+                let prev = b.step(dir.reverse());
+                self.grid[b] = self.grid[prev];
+                b = prev;
             }
 
-            self.grid[byi as usize][bxi as usize] = '.';
-            self.grid[(byi + dy) as usize][(bxi + dx) as usize] = '@';
-            return ((bxi + dx) as usize, (byi + dy) as usize);
+            self.grid[b] = '.';
+            let moved = b.step(dir);
+            self.grid[moved] = '@';
+            return moved;
         }
 
         let mut boundary = vec![];
+        let (nxi, nyi) = (next.x, next.y);
         let mut byi = nyi;
         boundary.push(nxi);
         if self.get(nxi, nyi) == ']' {
@@ -171,102 +159,70 @@ impl Day15 {
         for b in all_boundaries.iter().rev() {
             let pyi = byi - dy;
             for xi in b {
-                self.grid[byi as usize][*xi as usize] = self.get(*xi, pyi);
+                self.grid[Point::new(*xi, byi)] = self.get(*xi, pyi);
             }
             for xi in b {
-                self.grid[pyi as usize][*xi as usize] = '.';
+                self.grid[Point::new(*xi, pyi)] = '.';
             }
             byi = pyi;
         }
-        self.grid[byi as usize][start.0] = '@';
-        self.grid[(byi - dy) as usize][start.0] = '.';
-        (start.0, byi as usize)
+        self.grid[Point::new(start.x, byi)] = '@';
+        self.grid[Point::new(start.x, byi - dy)] = '.';
+        Point::new(start.x, byi)
         
     }
 }
 
 impl Day for Day15 {
-    fn part_1(&mut self) -> u64 {
-        self.load();
-        let mut start = Day15::get_start(&self.grid).expect("Start not found");
+    fn part_1(&self) -> Answer {
+        let mut warehouse = self.warehouse();
+        let grid = &mut warehouse.grid;
+        let mut start = grid.find(&'@').expect("Start not found");
         for dir in &self.movement {
-            let (dx, dy): (i32, i32) = match dir {
-                '^' => (0, -1),
-                '>' => (1, 0),
-                '<' => (-1, 0),
-                _ => (0, 1),
-            };
-            let (nxi, nyi) = (start.0 as i32 + dx, start.1 as i32 + dy);
-            let (nx, ny) = (nxi as usize, nyi as usize);
-            if self.grid[ny][nx] == '#' {
+            let next = start.step(*dir);
+            if grid[next] == '#' {
                 continue;
             }
 
-            if self.grid[ny][nx] == '.' {
-                self.grid[ny][nx] = '@';
-                self.grid[start.1][start.0] = '.';
-                start = (nx, ny);
+            if grid[next] == '.' {
+                grid[next] = '@';
+                grid[start] = '.';
+                start = next;
                 continue;
             }
 
-            let (mut bxi, mut byi) = (nxi, nyi);
-            while self.grid[byi as usize][bxi as usize] == 'O' {
-                bxi += dx;
-                byi += dy;
+            let mut b = next;
+            while grid[b] == 'O' {
+                b = b.step(*dir);
             }
 
-            if self.grid[byi as usize][bxi as usize] == '#' {
+            if grid[b] == '#' {
                 continue;
             }
 
-            while self.grid[byi as usize][bxi as usize] != '@' {
-                self.grid[byi as usize][bxi as usize] = 'O';
-                bxi -= dx;
-                byi -= dy;
+            while grid[b] != '@' {
+                grid[b] = 'O';
+                b = b.step(dir.reverse());
             }
 
-            self.grid[byi as usize][bxi as usize] = '.';
-            self.grid[(byi + dy) as usize][(bxi + dx) as usize] = '@';
-            start = ((bxi + dx) as usize, (byi + dy) as usize);
+            grid[b] = '.';
+            start = b.step(*dir);
+            grid[start] = '@';
         }
 
-        let mut total = 0;
-        for (y, l) in self.grid.iter().enumerate() {
-            for (x, ch) in l.iter().enumerate() {
-                if *ch == 'O' {
-                    total += 100 * y + x;
-                }
-            }
-        }
-        total as u64
+        gps_total(grid, 'O').into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        self.load();
-        self.modify_grid();
-        let mut start = Day15::get_start(&self.grid).expect("Start not found");
-        let movements = &self.movement.clone();
-        for dir in movements {
-            start = self.step(*dir, start);
-        }
-        println!("===================Final====================");
-        for l in self.grid.iter() {
-            for c in l.iter() {
-                print!("{c}");
-            }
-            println!();
-        }
-        println!("======================================");
-
-        let mut total = 0;
-        for (y, l) in self.grid.iter().enumerate() {
-            for (x, ch) in l.iter().enumerate() {
-                if *ch == '[' {
-                    total += 100 * y + x;
-                }
-            }
+    fn part_2(&self) -> Answer {
+        let mut warehouse = self.wide_warehouse();
+        let mut start = warehouse.start().expect("Start not found");
+        for dir in &self.movement {
+            start = warehouse.step(*dir, start);
         }
+        eprintln!("===================Final====================");
+        eprintln!("{}", warehouse.grid);
+        eprintln!("======================================");
 
-        total as u64
+        gps_total(&warehouse.grid, '[').into()
     }
 }
@@ -1,212 +1,62 @@
-use std::{collections::{HashMap, HashSet}, fs, u64};
+use std::collections::HashSet;
 
+use crate::answer::Answer;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
+use crate::search;
 use crate::traits::Day;
 
-#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
-pub enum DIR {
-    EAST,
-    WEST,
-    NORTH,
-    SOUTH,
-}
-
 pub struct Day16 {
-    grid: Vec<Vec<char>>,
+    grid: Grid<char>,
 }
 
 impl Day16 {
     pub fn new() -> Self {
-        let grid = fs::read_to_string("data/year2024/day16")
-            .expect("data file not found")
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect();
-
-        Day16 { grid }
+        parse::load("data/year2024/day16", Self::parse)
     }
 
-    pub fn find_char(&self, ch: &char) -> Option<(usize, usize)> {
-        for (y, line) in self.grid.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                if c == ch {
-                    return Some((x, y));
-                }
-            }
-        }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, input, "#.SE")?;
 
-        None
+        Ok(Day16 { grid })
     }
-}
 
-pub fn dfs(
-    grid: &Vec<Vec<char>>,
-    x: usize,
-    y: usize,
-    dir: DIR,
-    score: u64,
-    visited: &mut HashMap<(usize, usize, DIR), u64>,
-) -> u64 {
-    if let Some(sc) = visited.get(&(x, y, dir)) {
-        if *sc <= score {
-            return u64::MAX;
-        }
-    }
-    visited.insert((x, y, dir), score);
-
-    if grid[y][x] == 'E' {
-        return score;
-    }
-    let mut scores = vec![];
-    // west
-    if x > 0 && grid[y][x - 1] != '#' && dir != DIR::EAST {
-        let pen = match dir {
-            DIR::WEST => 1,
-            _ => 1001,
-        };
-        scores.push(dfs(grid, x - 1, y, DIR::WEST, score + pen, visited));
+    fn start(&self) -> Reindeer {
+        (self.grid.find(&'S').expect("Start not found"), Direction::East)
     }
 
-    // east
-    if x < grid[0].len() - 1 && grid[y][x + 1] != '#' && dir != DIR::WEST {
-        let pen = match dir {
-            DIR::EAST => 1,
-            _ => 1001,
-        };
-        scores.push(dfs(grid, x + 1, y, DIR::EAST, score + pen, visited));
+    fn is_end(&self, &(pos, _): &Reindeer) -> bool {
+        self.grid[pos] == 'E'
     }
-
-    // north
-    if y > 0 && grid[y - 1][x] != '#' && dir != DIR::SOUTH {
-        let pen = match dir {
-            DIR::NORTH => 1,
-            _ => 1001,
-        };
-        scores.push(dfs(grid, x, y - 1, DIR::NORTH, score + pen, visited));
-    }
-
-    // south
-    if y < grid.len() - 1 && grid[y + 1][x] != '#' && dir != DIR::NORTH {
-        let pen = match dir {
-            DIR::SOUTH => 1,
-            _ => 1001,
-        };
-        scores.push(dfs(grid, x, y + 1, DIR::SOUTH, score + pen, visited));
-    }
-
-    *scores.iter().min().unwrap_or(&u64::MAX)
 }
 
-pub fn dfs2(
-    grid: &Vec<Vec<char>>,
-    x: usize,
-    y: usize,
-    dir: DIR,
-    score: u64,
-    visited: &mut HashMap<(usize, usize, DIR), u64>,
-    path: &mut Vec<(usize, usize)>,
-) -> (u64, Vec<Vec<(usize, usize)>>) {
-    if let Some(sc) = visited.get(&(x, y, dir)) {
-        if *sc < score {
-            return (u64::MAX, vec![]);
-        }
-    }
-    visited.insert((x, y, dir), score);
-    path.push((x, y));
-
-    if grid[y][x] == 'E' {
-        let res = (score, vec![path.clone()]);
-        path.pop();
-        return res;
-    }
-
-    let mut best = u64::MAX;
-    let mut paths = vec![];
-
-    if x > 0 && grid[y][x - 1] != '#' && dir != DIR::EAST {
-        let pen = match dir {
-            DIR::WEST => 1,
-            _ => 1001,
-        };
-        let (child_score, child_paths) =
-            dfs2(grid, x - 1, y, DIR::WEST, score + pen, visited, path);
-        if child_score < best {
-            best = child_score;
-            paths = child_paths;
-        } else if child_score == best {
-            paths.extend(child_paths);
-        }
-    }
-
-    if x < grid[0].len() - 1 && grid[y][x + 1] != '#' && dir != DIR::WEST {
-        let pen = match dir {
-            DIR::EAST => 1,
-            _ => 1001,
-        };
-        let (child_score, child_paths) =
-            dfs2(grid, x + 1, y, DIR::EAST, score + pen, visited, path);
-        if child_score < best {
-            best = child_score;
-            paths = child_paths;
-        } else if child_score == best {
-            paths.extend(child_paths);
-        }
-    }
-
-    if y > 0 && grid[y - 1][x] != '#' && dir != DIR::SOUTH {
-        let pen = match dir {
-            DIR::NORTH => 1,
-            _ => 1001,
-        };
-        let (child_score, child_paths) =
-            dfs2(grid, x, y - 1, DIR::NORTH, score + pen, visited, path);
-        if child_score < best {
-            best = child_score;
-            paths = child_paths;
-        } else if child_score == best {
-            paths.extend(child_paths);
-        }
-    }
-
-    if y < grid.len() - 1 && grid[y + 1][x] != '#' && dir != DIR::NORTH {
-        let pen = match dir {
-            DIR::SOUTH => 1,
-            _ => 1001,
-        };
-        let (child_score, child_paths) =
-            dfs2(grid, x, y + 1, DIR::SOUTH, score + pen, visited, path);
-        if child_score < best {
-            best = child_score;
-            paths = child_paths;
-        } else if child_score == best {
-            paths.extend(child_paths);
-        }
-    }
-
-    path.pop();
-    (best, paths)
+/// Where the reindeer stands and which way it faces.
+type Reindeer = (Point, Direction);
+
+/// Moves out of `pos` facing `dir`, with the penalty of each.
+fn moves(grid: &Grid<char>, (pos, dir): Reindeer) -> impl Iterator<Item = (Reindeer, u64)> + '_ {
+    Direction::ALL.into_iter().filter_map(move |heading| {
+        let next = pos.step(heading);
+        let open = grid.get(next).is_some_and(|&c| c != '#');
+        let pen = if dir == heading { 1 } else { 1001 };
+        (open && dir != heading.reverse()).then_some(((next, heading), pen))
+    })
 }
 
 impl Day for Day16 {
-    fn part_1(&mut self) -> u64 {
-        let (x, y) = self.find_char(&'S').expect("Start not found");
-        let mut visited = HashMap::new();
-        dfs(&self.grid, x, y, DIR::EAST, 0, &mut visited)
+    fn part_1(&self) -> Answer {
+        search::dijkstra(self.start(), |&r| moves(&self.grid, r), |r| self.is_end(r))
+            .expect("End not reachable")
+            .cost
+            .into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let (x, y) = self.find_char(&'S').expect("Start not found");
-        let mut visited = HashMap::new();
-        let mut path = Vec::new();
-        let (_, all_paths) =
-            dfs2(&self.grid, x, y, DIR::EAST, 0, &mut visited, &mut path);
-
-        let mut path_set = HashSet::new();
-        for path in all_paths.iter() {
-            for p in path {
-                path_set.insert(*p);
-            }
-        }
+    fn part_2(&self) -> Answer {
+        let best = search::dijkstra_all(self.start(), |&r| moves(&self.grid, r), |r| self.is_end(r))
+            .expect("End not reachable");
 
-        path_set.len() as u64
+        let path_set: HashSet<Point> = best.states().into_iter().map(|(pos, _)| pos).collect();
+        path_set.len().into()
     }
 }
@@ -1,34 +1,53 @@
-use std::{collections::HashSet, fs};
+use std::collections::HashSet;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day17 {
+    registers: Registers,
+    program: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Registers {
     a: u64,
     b: u64,
     c: u64,
-    program: Vec<u64>,
 }
 
-pub fn line_to2(line: &str) -> &str {
-    line.split(": ").nth(1).unwrap()
+pub fn line_to2<'a>(input: &str, line: Option<&'a str>, expected: &str) -> Result<&'a str, ParseError> {
+    let line = parse::expect(input, line, &input[input.len()..], expected)?;
+    Ok(parse::split_once(input, line, ": ")?.1)
 }
 
 impl Day17 {
     pub fn new() -> Self {
-        let data_str = fs::read_to_string("data/year2024/day17").expect("Cannot read data file");
-        let mut data = data_str.lines();
-        let a: u64 = line_to2(data.next().unwrap()).parse().unwrap();
-        let b: u64 = line_to2(data.next().unwrap()).parse().unwrap();
-        let c: u64 = line_to2(data.next().unwrap()).parse().unwrap();
+        parse::load("data/year2024/day17", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut data = input.lines();
+        let a: u64 = parse::number(input, line_to2(input, data.next(), "register A")?)?;
+        let b: u64 = parse::number(input, line_to2(input, data.next(), "register B")?)?;
+        let c: u64 = parse::number(input, line_to2(input, data.next(), "register C")?)?;
         data.next();
-        let program: Vec<u64> = line_to2(data.next().unwrap())
+        let program: Vec<u64> = line_to2(input, data.next(), "a program")?
             .split(',')
-            .map(|c| c.parse().unwrap())
-            .collect();
+            .map(|c| match c {
+                "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => parse::number(input, c),
+                _ => Err(ParseError::at(input, c, "a 3-bit number")),
+            })
+            .collect::<Result<_, _>>()?;
+        if !program.len().is_multiple_of(2) {
+            return Err(ParseError::at(input, input.trim_end(), "opcode and operand pairs"));
+        }
 
-        Day17 { a, b, c, program }
+        Ok(Day17 { registers: Registers { a, b, c }, program })
     }
+}
 
+impl Registers {
     #[inline(always)]
     pub fn operation(&mut self, opcode: u64, operand: u64) -> (Option<usize>, Option<u64>) {
         let combo = match operand {
@@ -84,7 +103,8 @@ impl Day17 {
 }
 
 impl Day for Day17 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
+        let mut registers = self.registers;
         let mut i = 0;
         let mut solution = Vec::new();
         let program_len = self.program.len();
@@ -95,7 +115,7 @@ impl Day for Day17 {
             if operand == 7 && opcode != 4 {
                 panic!("Invalid operand");
             }
-            let (shift_i, output) = self.operation(opcode, operand);
+            let (shift_i, output) = registers.operation(opcode, operand);
             if let Some(s_i) = shift_i {
                 i = s_i;
                 continue;
@@ -106,34 +126,30 @@ impl Day for Day17 {
                 solution.push(res.to_string());
             }
         }
-        let output = solution.join(",");
-        println!("Solution = {}", output);
-        0
+        solution.join(",").into()
     }
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let program_len = self.program.len();
         let mut a = 8u64.pow(program_len as u32) / 8;
         let limit = a * 8;
         loop {
-            self.a = a;
-            self.b = 0;
-            self.c = 0;
+            let mut registers = Registers { a, b: 0, c: 0 };
             let mut i = 0;
             let mut states = HashSet::new();
             let mut output = vec![];
 
 
             while i < program_len {
-                if states.contains(&(self.a, self.b, self.c, i)) {
+                if states.contains(&(registers, i)) {
                     break;
                 }
-                states.insert((self.a, self.b, self.c, i));
+                states.insert((registers, i));
                 let opcode = self.program[i];
                 let operand = self.program[i + 1];
                 if operand == 7 && opcode != 4 {
                     panic!("Invalid operand");
                 }
-                let (shift_i, out) = self.operation(opcode, operand);
+                let (shift_i, out) = registers.operation(opcode, operand);
                 
                 if let Some(s_i) = shift_i {
                     i = s_i;
@@ -145,7 +161,7 @@ impl Day for Day17 {
                 if let Some(res) = out {
                     output.push(res);
                     if output == self.program {
-                        return a;
+                        return a.into();
                     }
                 }
             }
@@ -157,8 +173,28 @@ impl Day for Day17 {
                 panic!("limit reached");
             }
             let out_str = output.iter().map(|a| a.to_string()).collect::<Vec<String>>().join(",");
-            println!("a = {a} ;; Out = {out_str}");
+            eprintln!("a = {a} ;; Out = {out_str}");
             
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2024::day17::Day17;
+
+    const SAMPLE: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+    #[test]
+    fn test_parts_are_repeatable() {
+        let day = Day17::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), "4,6,3,5,6,3,5,2,1,0".into());
+        assert_eq!(day.part_1(), "4,6,3,5,6,3,5,2,1,0".into());
+    }
+}
@@ -1,106 +1,66 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-};
 use rayon::prelude::*;
 
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
+use crate::search::{self, Path};
 use crate::traits::Day;
 
 pub struct Day18 {
-    bytes: Vec<(usize, usize)>,
+    bytes: Vec<Point>,
     x_lim: usize,
 }
 
 impl Day18 {
     pub fn new() -> Self {
-        let bytes = fs::read_to_string("data/year2024/day18")
-            .expect("Cannot read data file")
+        parse::load("data/year2024/day18", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let bytes = input
             .lines()
             .map(|x| {
-                let mut parts = x.split(',');
-                (
-                    parts.next().unwrap().parse().unwrap(),
-                    parts.next().unwrap().parse().unwrap(),
-                )
+                let (a, b) = parse::split_once(input, x, ",")?;
+                Ok(Point::new(parse::number(input, a)?, parse::number(input, b)?))
             })
-            .collect();
-
-        Day18 { bytes, x_lim: 70 }
-    }
-}
+            .collect::<Result<_, ParseError>>()?;
 
-pub fn dfs(
-    bytes: &HashSet<(usize, usize)>,
-    x: usize,
-    y: usize,
-    count: u64,
-    lim: usize,
-    visited: &mut HashMap<(usize, usize), u64>,
-) -> u64 {
-    if x == lim && y == lim {
-        return count;
+        Ok(Day18 { bytes, x_lim: 70 })
     }
 
-    if let Some(steps) = visited.get(&(x, y)) {
-        if *steps <= count {
-            return u64::MAX;
+    /// The memory space once the first `count` bytes have fallen.
+    fn corrupted(&self, count: usize) -> Grid<bool> {
+        let mut memory = Grid::new(self.x_lim + 1, self.x_lim + 1, false);
+        for &byte in self.bytes.iter().take(count) {
+            if let Some(cell) = memory.get_mut(byte) {
+                *cell = true;
+            }
         }
+        memory
     }
 
-    visited.insert((x, y), count);
-
-    if bytes.contains(&(x, y)) {
-        return u64::MAX;
-    }
-
-    let mut all_dir = vec![];
-
-    if x > 0 {
-        all_dir.push(dfs(bytes, x - 1, y, count + 1, lim, visited));
+    /// Shortest way through the memory space once `count` bytes have fallen, if any.
+    fn escape(&self, count: usize) -> Option<Path<Point, usize>> {
+        let bytes = self.corrupted(count);
+        let end = Point::new(self.x_lim as i32, self.x_lim as i32);
+        let open = |&pos: &Point| bytes.neighbours4(pos).filter(|&next| !bytes[next]).collect::<Vec<_>>();
+        search::bfs(Point::ORIGIN, open, |&pos| pos == end)
     }
-
-    if x < lim {
-        all_dir.push(dfs(bytes, x + 1, y, count + 1, lim, visited));
-    }
-
-    if y > 0 {
-        all_dir.push(dfs(bytes, x, y - 1, count + 1, lim, visited));
-    }
-
-    if y < lim {
-        all_dir.push(dfs(bytes, x, y + 1, count + 1, lim, visited));
-    }
-
-    *all_dir.iter().min().unwrap_or(&u64::MAX)
 }
 
 impl Day for Day18 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let sim = 1024;
-        let bytes: HashSet<(usize, usize)> = self.bytes.iter().take(sim).cloned().collect();
-        let mut visited = HashMap::new();
-        dfs(&bytes, 0, 0, 0, self.x_lim, &mut visited)
+        self.escape(sim).map_or(u64::MAX, |path| path.cost as u64).into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        let result = (0..self.bytes.len())
+    fn part_2(&self) -> Answer {
+        let sim = (0..self.bytes.len())
             .into_par_iter()
-            .find_any(|&sim| {
-                let bytes: HashSet<(usize, usize)> = self.bytes.iter().take(sim).cloned().collect();
-                let mut visited = HashMap::new();
-                let dfs_result = dfs(&bytes, 0, 0, 0, self.x_lim, &mut visited);
-                if dfs_result == u64::MAX {
-                    let res = self.bytes[sim-1];
-                    println!("{},{}", res.0, res.1);
-                    true
-                } else {
-                    false
-                }
-            });
-        if let Some(_) = result {
-            0
-        } else {
-            unimplemented!()
-        }
+            .find_first(|&sim| self.escape(sim).is_none())
+            .expect("Exit is never blocked");
+        let byte = self.bytes[sim - 1];
+        Answer::Coord(byte.x as i64, byte.y as i64)
     }
 }
@@ -1,8 +1,10 @@
 use dashmap::DashMap;
 use rayon::prelude::*;
 use std::sync::Arc;
-use std::{collections::HashSet, fs};
+use std::collections::HashSet;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day19 {
@@ -13,11 +15,12 @@ pub struct Day19 {
 
 impl Day19 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2024/day19").expect("Cannot read data file");
-        let mut parts = data.lines();
-        let towels = parts
-            .next()
-            .unwrap()
+        parse::load("data/year2024/day19", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parts = input.lines();
+        let towels = parse::expect(input, parts.next(), input, "a list of towels")?
             .split(", ")
             .map(String::from)
             .collect::<Vec<String>>();
@@ -28,13 +31,14 @@ impl Day19 {
             .iter()
             .map(|towel| towel.len())
             .max()
-            .expect("No towels found");
+            .filter(|&max| max > 0)
+            .ok_or_else(|| ParseError::at(input, input, "a list of towels"))?;
 
-        Day19 {
+        Ok(Day19 {
             towels,
             patterns,
             max,
-        }
+        })
     }
 }
 
@@ -75,7 +79,7 @@ fn dfs2(towels: &HashSet<String>, pattern: &str, max: usize, cache: &DashMap<Str
 
 
 impl Day for Day19 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let cache = Arc::new(DashMap::new());
         self.patterns
             .par_iter()
@@ -83,10 +87,11 @@ impl Day for Day19 {
                 let cache = Arc::clone(&cache);
                 dfs(&self.towels, *pattern, self.max, &cache)
             })
-            .count() as u64
+            .count()
+            .into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let cache = Arc::new(DashMap::new());
         self.patterns
             .par_iter()
@@ -94,6 +99,6 @@ impl Day for Day19 {
                 let cache = Arc::clone(&cache);
                 dfs2(&self.towels, pattern, self.max, &cache)
             })
-            .sum()
+            .sum::<u64>().into()
     }
 }
@@ -1,5 +1,6 @@
-use std::fs;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day2 {
@@ -8,17 +9,20 @@ pub struct Day2 {
 
 impl Day2 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2024/day2")
-            .expect("cannot read to file")
-            .split('\n')
+        parse::load("data/year2024/day2", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
             .map(|line| {
                 line.split_whitespace()
-                    .map(|i| i.parse().expect("cannot parse number"))
-                    .collect::<Vec<i32>>()
+                    .map(|i| parse::number(input, i))
+                    .collect::<Result<Vec<i32>, _>>()
             })
-            .collect::<Vec<Vec<i32>>>();
+            .collect::<Result<Vec<Vec<i32>>, _>>()?;
 
-        Day2 { lines }
+        Ok(Day2 { lines })
     }
 
     pub fn is_safe(&self, arr: &Vec<i32>) -> bool {
@@ -43,17 +47,17 @@ impl Day2 {
 }
 
 impl Day for Day2 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut count = 0;
         for arr in &self.lines {
             if self.is_safe(arr) {
                 count += 1;
             }
         }
-        count
+        count.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut count = 0;
         for arr in &self.lines {
             if self.is_safe(arr) {
@@ -69,6 +73,6 @@ impl Day for Day2 {
                 }
             }
         }
-        count
+        count.into()
     }
 }
@@ -1,129 +1,115 @@
+use crate::answer::Answer;
+use crate::geom::{Direction, Point};
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use ahash::HashMap;
-use std::{fs, hash::Hash, u8};
-
-// A -> 0
-// > -> 1
-// ^ -> 2
-// v -> 3
-// < -> 4
-
-const RIGHT: u8 = 4;
-const UP: u8 = 2;
-const DOWN: u8 = 3;
-const LEFT: u8 = 1;
-const A: u8 = 0;
+use std::fmt;
 
+/// A button on the directional keypad.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point(i32, i32);
+enum Key {
+    Arrow(Direction),
+    Activate,
+}
 
-impl Point {
-    fn diff(&self, other: &Point) -> Point {
-        Point(self.0 - other.0, self.1 - other.1)
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Arrow(dir) => write!(f, "{dir}"),
+            Key::Activate => write!(f, "A"),
+        }
     }
 }
 
 pub struct Day21 {
     numbers: Vec<String>,
     number_to_coord: HashMap<char, Point>,
-    direction_to_coord: HashMap<u8, Point>,
+    direction_to_coord: HashMap<Key, Point>,
 }
 
 impl Day21 {
     pub fn new() -> Self {
-        let numbers = fs::read_to_string("data/year2024/day21")
-            .expect("Cannot read data file")
+        parse::load("data/year2024/day21", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers = input
             .lines()
-            .map(String::from)
-            .collect();
+            .map(|l| match l.char_indices().find(|&(_, c)| !"0123456789A".contains(c)) {
+                Some((i, c)) => Err(ParseError::at(input, &l[i..i + c.len_utf8()], "a door code character")),
+                None => Ok(String::from(l)),
+            })
+            .collect::<Result<_, _>>()?;
 
         let mut number_to_coord = HashMap::default();
-        number_to_coord.insert('7', Point(0, 0));
-        number_to_coord.insert('8', Point(1, 0));
-        number_to_coord.insert('9', Point(2, 0));
-        number_to_coord.insert('4', Point(0, 1));
-        number_to_coord.insert('5', Point(1, 1));
-        number_to_coord.insert('6', Point(2, 1));
-        number_to_coord.insert('1', Point(0, 2));
-        number_to_coord.insert('2', Point(1, 2));
-        number_to_coord.insert('3', Point(2, 2));
-        number_to_coord.insert('0', Point(1, 3));
-        number_to_coord.insert('A', Point(2, 3));
+        number_to_coord.insert('7', Point::new(0, 0));
+        number_to_coord.insert('8', Point::new(1, 0));
+        number_to_coord.insert('9', Point::new(2, 0));
+        number_to_coord.insert('4', Point::new(0, 1));
+        number_to_coord.insert('5', Point::new(1, 1));
+        number_to_coord.insert('6', Point::new(2, 1));
+        number_to_coord.insert('1', Point::new(0, 2));
+        number_to_coord.insert('2', Point::new(1, 2));
+        number_to_coord.insert('3', Point::new(2, 2));
+        number_to_coord.insert('0', Point::new(1, 3));
+        number_to_coord.insert('A', Point::new(2, 3));
 
         let mut direction_to_coord = HashMap::default();
-        direction_to_coord.insert(UP, Point(1, 0));
-        direction_to_coord.insert(LEFT, Point(0, 1));
-        direction_to_coord.insert(DOWN, Point(1, 1));
-        direction_to_coord.insert(RIGHT, Point(2, 1));
-        direction_to_coord.insert(A, Point(2, 0));
+        direction_to_coord.insert(Key::Arrow(Direction::North), Point::new(1, 0));
+        direction_to_coord.insert(Key::Arrow(Direction::West), Point::new(0, 1));
+        direction_to_coord.insert(Key::Arrow(Direction::South), Point::new(1, 1));
+        direction_to_coord.insert(Key::Arrow(Direction::East), Point::new(2, 1));
+        direction_to_coord.insert(Key::Activate, Point::new(2, 0));
 
-        Day21 {
+        Ok(Day21 {
             numbers,
             number_to_coord,
             direction_to_coord,
-        }
+        })
     }
 
-    fn digit_to_sequence(&self, start: &Point, digit: char) -> Vec<u8> {
-        let mut sequence = Vec::with_capacity(16);
+    fn digit_to_sequence(&self, start: &Point, digit: char) -> Vec<Key> {
         let pos = self.number_to_coord.get(&digit).unwrap();
-        let diff = start.diff(pos);
-        for _ in 0..diff.0.abs() {
-            sequence.push(if diff.0 > 0 { LEFT } else { RIGHT });
-        }
-        for _ in 0..diff.1.abs() {
-            sequence.push(if diff.1 > 0 { UP } else { DOWN });
-        }
+        let mut arrows = arrows(*start - *pos);
 
-        if (start.1 == 3 && pos.0 == 0) || (start.0 == 0 && pos.1 == 3) {
-            d2s_sort(&mut sequence);
+        if (start.y == 3 && pos.x == 0) || (start.x == 0 && pos.y == 3) {
+            d2s_sort(&mut arrows);
         }  else {
-            sequence.sort();
+            default_sort(&mut arrows);
         }
 
-        
-        sequence.push(A);
-        sequence
+        press(arrows)
     }
 
     fn sequence_to_sequence(
         &self,
         start: &Point,
-        seq: u8,
-        cache: &mut HashMap<(Point, u8), Vec<u8>>,
-    ) -> Vec<u8> {
+        seq: Key,
+        cache: &mut HashMap<(Point, Key), Vec<Key>>,
+    ) -> Vec<Key> {
         if let Some(cached_seq) = cache.get(&(*start, seq)) {
             return cached_seq.clone();
         }
-        let start = start;
         let pos = self.direction_to_coord.get(&seq).unwrap();
-        let mut sequence = Vec::new();
-        
-        let diff = start.diff(&pos);
-        for _ in 0..diff.0.abs() {
-            sequence.push(if diff.0 > 0 { LEFT } else { RIGHT });
-        }
-        for _ in 0..diff.1.abs() {
-            sequence.push(if diff.1 > 0 { UP } else { DOWN });
-        }
+        let mut arrows = arrows(*start - *pos);
 
-        if (start.1 == 1 && start.0 == 0 && pos.1 == 0) || (start.1 == 0 && pos.0 == 0 && pos.1 == 1) {
-            s2s_sort(&mut sequence);
+        if (start.y == 1 && start.x == 0 && pos.y == 0) || (start.y == 0 && pos.x == 0 && pos.y == 1) {
+            s2s_sort(&mut arrows);
         } else {
-            sequence.sort();
+            default_sort(&mut arrows);
         }
-        sequence.push(A);
+        let sequence = press(arrows);
         cache.insert((*start, seq), sequence.clone());
         sequence
     }
 
     fn dfs_sequence_length(
         &self,
-        sequence: &[u8],
+        sequence: &[Key],
         depth: usize,
-        direction_map: &HashMap<u8, Point>,
-        seq_cache: &mut HashMap<(Point, u8), Vec<u8>>,
-        cache: &mut HashMap<(Vec<u8>, usize), usize>
+        direction_map: &HashMap<Key, Point>,
+        seq_cache: &mut HashMap<(Point, Key), Vec<Key>>,
+        cache: &mut HashMap<(Vec<Key>, usize), usize>
     ) -> usize {
         if depth == 0 {
             return sequence.len();
@@ -133,7 +119,7 @@ impl Day21 {
             return *cached_length;
         }
 
-        let mut d_start = direction_map[&A];
+        let mut d_start = direction_map[&Key::Activate];
         let mut sum = 0;
         for &c in sequence {
             let seq = self.sequence_to_sequence(&d_start, c, seq_cache);
@@ -145,31 +131,58 @@ impl Day21 {
     }
 }
 
-fn d2s_sort(seq: &mut Vec<u8>) {
-    fn custom_rank(n: u8) -> u8 {
-        match n {
-            UP => 0,
-            RIGHT => 1,
-            DOWN => 2,
-            LEFT => 3,
-            _ => 4,
+/// Arrows moving a keypad arm by `-diff`, horizontal ones first.
+fn arrows(diff: Point) -> Vec<Direction> {
+    let mut arrows = Vec::with_capacity(16);
+    for _ in 0..diff.x.abs() {
+        arrows.push(if diff.x > 0 { Direction::West } else { Direction::East });
+    }
+    for _ in 0..diff.y.abs() {
+        arrows.push(if diff.y > 0 { Direction::North } else { Direction::South });
+    }
+    arrows
+}
+
+/// The keys pressing `arrows` in order, then `A`.
+fn press(arrows: Vec<Direction>) -> Vec<Key> {
+    arrows.into_iter().map(Key::Arrow).chain([Key::Activate]).collect()
+}
+
+fn default_sort(seq: &mut [Direction]) {
+    fn custom_rank(dir: Direction) -> u8 {
+        match dir {
+            Direction::West => 0,
+            Direction::North => 1,
+            Direction::South => 2,
+            Direction::East => 3,
+        }
+    }
+    seq.sort_by_key(|&x| custom_rank(x));
+}
+
+fn d2s_sort(seq: &mut [Direction]) {
+    fn custom_rank(dir: Direction) -> u8 {
+        match dir {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
         }
     }
 
     seq.sort_by_key(|&x| custom_rank(x));
 }
 
-fn s2s_sort(seq: &mut Vec<u8>) {
-    fn custom_rank(n: u8) -> u8 {
-        match n {
-            UP => 1,
-            RIGHT => 0,
-            DOWN => 2,
-            LEFT => 3,
-            _ => 4,
+fn s2s_sort(seq: &mut [Direction]) {
+    fn custom_rank(dir: Direction) -> u8 {
+        match dir {
+            Direction::East => 0,
+            Direction::North => 1,
+            Direction::South => 2,
+            Direction::West => 3,
         }
-    }   
-   seq.sort_by_key(|&x| custom_rank(x));
+    }
+    seq.sort_by_key(|&x| custom_rank(x));
 }
 
 
@@ -179,16 +192,7 @@ fn string_number_to_num(number: &str) -> u64 {
 }
 
 impl Day for Day21 {
-    fn part_1(&mut self) -> u64 {
-        
-        let u8_to_char = HashMap::from_iter(vec![
-            (A, 'A'),
-            (RIGHT, '>'),
-            (UP, '^'),
-            (DOWN, 'v'),
-            (LEFT, '<'),
-        ]);
-
+    fn part_1(&self) -> Answer {
         let number_map = &self.number_to_coord;
         let direction_map = &self.direction_to_coord;
         self.numbers
@@ -202,41 +206,41 @@ impl Day for Day21 {
                     start = number_map[&digit];
                 }
                 // print sequence
-                print!("Sequence: ");
+                eprint!("Sequence: ");
                 for seq in &sequence {
-                    print!("{}", u8_to_char[seq]);
+                    eprint!("{}", seq);
                 }
-                println!();
+                eprintln!();
                 let mut s2s = Vec::new();
-                let mut s_start = direction_map[&A];
+                let mut s_start = direction_map[&Key::Activate];
                 for seq in &sequence {
                     s2s.extend(self.sequence_to_sequence(&s_start, *seq, &mut cache));
                     s_start = direction_map[seq];
                 }
-                print!("S2S: ");
+                eprint!("S2S: ");
                 for seq in &s2s {
-                    print!("{}", u8_to_char[seq]);
+                    eprint!("{}", seq);
                 }
-                println!();
+                eprintln!();
                 let mut final_seq = Vec::new();
-                let mut f_start = direction_map[&A];
+                let mut f_start = direction_map[&Key::Activate];
                 for seq in &s2s {
                     final_seq.extend(self.sequence_to_sequence(&f_start, *seq, &mut cache));
                     f_start = direction_map[seq];
                 }
-                print!("Final: ");
+                eprint!("Final: ");
                 let num = string_number_to_num(sample);
                 for seq in &final_seq {
-                    print!("{}", u8_to_char[seq]);
+                    eprint!("{}", seq);
                 }
-                println!(";; Len: {}, num: {}", final_seq.len(), num);
-                println!();
+                eprintln!(";; Len: {}, num: {}", final_seq.len(), num);
+                eprintln!();
                 final_seq.len() as u64 * num
             })
-            .sum()
+            .sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let number_map = &self.number_to_coord;
         let direction_map = &self.direction_to_coord;
         let mut seq_cache = HashMap::default();
@@ -260,6 +264,6 @@ impl Day for Day21 {
                 let num = string_number_to_num(sample);
                 length as u64 * num
             })
-            .sum()
+            .sum::<u64>().into()
     }
 }
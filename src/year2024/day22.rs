@@ -1,5 +1,7 @@
-use std::{fs, u8};
+use std::u8;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use ahash::{HashMap, HashSet, RandomState};
 use rayon::prelude::*;
@@ -10,13 +12,16 @@ pub struct Day22 {
 
 impl Day22 {
     pub fn new() -> Self {
-        let numbers = fs::read_to_string("data/year2024/day22")
-            .expect("Cannot read data file")
+        parse::load("data/year2024/day22", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers = input
             .lines()
-            .map(|l| l.parse().unwrap())
-            .collect();
+            .map(|l| parse::number(input, l))
+            .collect::<Result<_, _>>()?;
 
-        Day22 { numbers }
+        Ok(Day22 { numbers })
     }
 }
 
@@ -28,7 +33,7 @@ fn process(input: u64) -> u64 {
 }
 
 impl Day for Day22 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.numbers
             .par_iter()
             .map(|num| {
@@ -38,10 +43,10 @@ impl Day for Day22 {
                 }
                 n
             })
-            .sum()
+            .sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let digits: Vec<Vec<u8>> = self
             .numbers
             .iter()
@@ -111,5 +116,6 @@ impl Day for Day22 {
             })
             .max()
             .expect("No max found")
+            .into()
     }
 }
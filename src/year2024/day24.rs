@@ -1,7 +1,8 @@
 use ahash::{HashMap, HashSet};
 use rand::{rngs::ThreadRng};
-use std::fs;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 const AND: u8 = 0;
@@ -20,36 +21,39 @@ pub struct Day24 {
 
 impl Day24 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2024/day24").unwrap();
-        let mut parts = data.split("\n\n");
+        parse::load("data/year2024/day24", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let (wires, gates) = parse::split_once(input, input, "\n\n")?;
         let mut inputs = HashMap::default();
         let mut dag = HashMap::default();
 
-        for line in parts.next().unwrap().lines() {
-            let mut section = line.split(": ");
-            let wire = section.next().unwrap();
-            let value = section.next().unwrap();
-            let value = if value == "1" { true } else { false };
+        for line in wires.lines() {
+            let (wire, value) = parse::split_once(input, line, ": ")?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(ParseError::at(input, value, "`0` or `1`")),
+            };
             inputs.insert(wire.to_string(), value);
         }
 
-        for line in parts.next().unwrap().lines() {
-            let mut section = line.split(" -> ");
-            let mut input_parts = section.next().unwrap().split(" ");
-            let a = input_parts.next().unwrap();
-            let op = match input_parts.next() {
-                Some("AND") => AND,
-                Some("OR") => OR,
-                Some("XOR") => XOR,
-                _ => panic!("Invalid operation"),
+        for line in gates.lines() {
+            let (gate, output) = parse::split_once(input, line, " -> ")?;
+            let (a, rest) = parse::split_once(input, gate, " ")?;
+            let (op, b) = parse::split_once(input, rest, " ")?;
+            let op = match op {
+                "AND" => AND,
+                "OR" => OR,
+                "XOR" => XOR,
+                _ => return Err(ParseError::at(input, op, "`AND`, `OR` or `XOR`")),
             };
-            let b = input_parts.next().unwrap();
 
-            let output = section.next().unwrap();
             dag.insert(output.to_string(), (a.to_string(), op, b.to_string()));
         }
 
-        Day24 { inputs, dag }
+        Ok(Day24 { inputs, dag })
     }
 }
 
@@ -124,7 +128,7 @@ fn complete_circuit(graph: &Graph, inputs: &HashMap<String, bool>) -> Result<Vec
 
 #[allow(dead_code)]
 fn print_tree(tree: &Graph, root: &str, indent: usize) {
-    println!("{}{}", " ".repeat(indent * 4), root);
+    eprintln!("{}{}", " ".repeat(indent * 4), root);
 
     if let Some((left, _, right)) = tree.get(root) {
         print_tree(tree, left, indent + 1);
@@ -249,9 +253,9 @@ fn print_bool_vec(bin: &Vec<bool>, rev: bool) {
     };
 
     for b in iter {
-        print!("{}", if *b { 1 } else { 0 });
+        eprint!("{}", if *b { 1 } else { 0 });
     }
-    println!();
+    eprintln!();
 }
 
 fn produce_input_map(x: u64, y: u64) -> HashMap<String, bool> {
@@ -305,7 +309,7 @@ fn print_graph_nodes(graph: &Graph) {
                 }
             })
             .collect();
-        println!("{z} = {:?}", ch);
+        eprintln!("{z} = {:?}", ch);
     }
 }
 
@@ -403,103 +407,104 @@ fn do_swap(dag: &mut Graph, swap: (String, String)) {
     dag.insert(swap.1.to_string(), temp1);
 }
 
-fn solution_to_string(solution: &Vec<(String, String)>) -> String {
+fn solution_wires(solution: &[(String, String)]) -> Vec<String> {
     let mut result = vec![];
     for s in solution {
         result.push(s.0.to_string());
         result.push(s.1.to_string());
     }
     result.sort();
+    result
+}
 
-    result.join(",")
+fn solution_to_string(solution: &Vec<(String, String)>) -> String {
+    solution_wires(solution).join(",")
 }
 
 impl Day for Day24 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let result = complete_circuit(&self.dag, &self.inputs).expect("No solution found");
-        bin_vec_to_num(&result)
+        bin_vec_to_num(&result).into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
+        let mut dag = self.dag.clone();
         let mut all_solutions = vec![];
-        let swaps1 = one_iter(&mut self.dag, 0).expect("First iteration failed");
+        let swaps1 = one_iter(&mut dag, 0).expect("First iteration failed");
         for swap in swaps1.iter() {
-            do_swap(&mut self.dag, swap.clone());
-            let swaps2 = one_iter(&mut self.dag, 1);
+            do_swap(&mut dag, swap.clone());
+            let swaps2 = one_iter(&mut dag, 1);
             if swaps2.is_err() {
-                do_swap(&mut self.dag, swap.clone());
+                do_swap(&mut dag, swap.clone());
                 continue;
             }
             let swaps2 = swaps2.unwrap();
             for swap2 in swaps2.iter() {
-                do_swap(&mut self.dag, swap2.clone());
-                let swaps3 = one_iter(&mut self.dag, 2);
+                do_swap(&mut dag, swap2.clone());
+                let swaps3 = one_iter(&mut dag, 2);
                 if swaps3.is_err() {
-                    do_swap(&mut self.dag, swap2.clone());
+                    do_swap(&mut dag, swap2.clone());
                     continue;
                 }
                 let swaps3 = swaps3.unwrap();
                 for swap3 in swaps3.iter() {
-                    do_swap(&mut self.dag, swap3.clone());
-                    let swaps4 = one_iter(&mut self.dag, 3);
+                    do_swap(&mut dag, swap3.clone());
+                    let swaps4 = one_iter(&mut dag, 3);
                     if swaps4.is_err() {
-                        do_swap(&mut self.dag, swap3.clone());
+                        do_swap(&mut dag, swap3.clone());
                         continue;
                     }
                     let swaps4 = swaps4.unwrap();
                     for swap4 in swaps4.iter() {
-                        do_swap(&mut self.dag, swap4.clone());
-                        let res = one_iter(&mut self.dag, 4);
+                        do_swap(&mut dag, swap4.clone());
+                        let res = one_iter(&mut dag, 4);
                         if res.is_err() {
-                            do_swap(&mut self.dag, swap4.clone());
+                            do_swap(&mut dag, swap4.clone());
                             continue;
                         }
                         let sol = vec![swap.clone(), swap2.clone(), swap3.clone(), swap4.clone()];
                         all_solutions.push(sol);
 
-                        do_swap(&mut self.dag, swap4.clone());
+                        do_swap(&mut dag, swap4.clone());
                     }
-                    do_swap(&mut self.dag, swap3.clone());
+                    do_swap(&mut dag, swap3.clone());
                 }
-                do_swap(&mut self.dag, swap2.clone());
+                do_swap(&mut dag, swap2.clone());
             }
-            do_swap(&mut self.dag, swap.clone());
+            do_swap(&mut dag, swap.clone());
         }
 
         let mut rng = rand::thread_rng();
         let mut visited = HashSet::default();
+        let mut found_solution = None;
         for solution in all_solutions {
             let s_str = solution_to_string(&solution);
             if visited.contains(&s_str) {
                 continue;
             }
             for sol in solution.iter() {
-                do_swap(&mut self.dag, sol.clone());
+                do_swap(&mut dag, sol.clone());
             }
             visited.insert(s_str);
 
             let mut found = true;
             for _ in 0..2 {
-                let fail_point = get_fail_point(&self.dag, &mut rng, 2);
+                let fail_point = get_fail_point(&dag, &mut rng, 2);
                 if fail_point.is_some() {
                     found = false;
                     break;
                 }
             }
-            if found {
-                println!(
-                    "Found solution: {:?} ;; {}",
-                    solution,
-                    solution_to_string(&solution)
-                );
+            if found && found_solution.is_none() {
+                found_solution = Some(solution_wires(&solution));
             }
 
             for sol in solution.iter().rev() {
-                do_swap(&mut self.dag, sol.clone());
+                do_swap(&mut dag, sol.clone());
             }
         }
-        // regression_test_input(&mut self.dag);
-        0
+        // regression_test_input(&mut dag);
+        found_solution.expect("No set of swaps fixes the adder").into()
     }
 }
 
@@ -531,7 +536,7 @@ fn regression_test_input(dag: &mut Graph) {
         for _ in 0..1 {
             let fail_point = get_fail_point(&dag, &mut rng, 40);
             if fail_point.is_some() {
-                println!("Bad: {:?} ;; {}", solution, solution_to_string(&solution));
+                eprintln!("Bad: {:?} ;; {}", solution, solution_to_string(&solution));
                 break;
             }
         }
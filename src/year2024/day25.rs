@@ -1,5 +1,7 @@
-use std::fs;
 
+use crate::answer::Answer;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 const HEIGHT: u8 = 5;
@@ -11,30 +13,29 @@ pub struct Day25 {
 
 impl Day25 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2024/day25").expect("Cannot read data file");
+        parse::load("data/year2024/day25", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
 
-        let parts = data.split("\n\n").collect::<Vec<&str>>();
+        let parts = input.split("\n\n").collect::<Vec<&str>>();
 
         let mut keys = vec![];
         let mut locks = vec![];
 
         for object in parts {
-            let lines = object.lines();
-            let mut obj = [0; 5];
-            for l in lines {
-                for (i, c) in l.chars().enumerate() {
-                    if c == '#' {
-                        obj[i] += 1;
-                    }
-                }
+            let schematic = Grid::parse(input, object, "#.")?;
+            if schematic.width() != 5 {
+                let first_row = object.lines().next().unwrap_or(object);
+                return Err(ParseError::at(input, first_row, "a schematic five columns wide"));
             }
-
-            for i in 0..5 {
-                obj[i] -= 1;
+            let mut obj = [0; 5];
+            for (i, column) in schematic.columns().enumerate() {
+                obj[i] = column.filter(|&&c| c == '#').count() as u8 - 1;
             }
 
             // check if all values are #
-            let is_key = object.lines().next().unwrap().chars().all(|c| c == '#');
+            let is_key = schematic.row(0).iter().all(|&c| c == '#');
             if is_key {
                 keys.push(obj);
             } else {
@@ -42,12 +43,12 @@ impl Day25 {
             }
         }
 
-        Day25 { keys, locks }
+        Ok(Day25 { keys, locks })
     }
 }
 
 impl Day for Day25 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut count = 0;
         for key in &self.keys {
             for lock in &self.locks {
@@ -57,10 +58,10 @@ impl Day for Day25 {
             }
         }
 
-        count
+        count.into()
     }
 
-    fn part_2(&mut self) -> u64 {
-        0
+    fn part_2(&self) -> Answer {
+        0u64.into()
     }
 }
@@ -1,6 +1,8 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 use regex::Regex;
-use std::{fs, usize};
+use std::usize;
 
 pub struct Day3 {
     line: String,
@@ -24,13 +26,17 @@ fn find_closest_smaller(arr: &[usize], target: usize) -> Option<usize> {
 
 impl Day3 {
     pub fn new() -> Self {
-        let line = fs::read_to_string("data/year2024/day3").expect("cannot read data");
-        Day3 { line }
+        parse::load("data/year2024/day3", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let line = input.to_string();
+        Ok(Day3 { line })
     }
 }
 
 impl Day for Day3 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let pattern = r"mul\((\d{1,3}),(\d{1,3})\)";
         let re = Regex::new(&pattern).unwrap();
         let mut sum = 0;
@@ -39,10 +45,10 @@ impl Day for Day3 {
             let b: u64 = cap.get(2).unwrap().as_str().parse().expect("cannot parse");
             sum += a * b;
         }
-        sum
+        sum.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let do_pat = r"do\(\)";
         let dont_pat = r"don't\(\)";
         let do_re = Regex::new(&do_pat).unwrap();
@@ -79,6 +85,6 @@ impl Day for Day3 {
                 sum += a * b;
             }
         }
-        sum
+        sum.into()
     }
 }
@@ -1,89 +1,57 @@
-use std::fs;
-
+use crate::answer::Answer;
+use crate::geom::{Point, ALL_AROUND};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day4 {
-    lines: Vec<Vec<char>>,
-    x_lim: i32,
-    y_lim: i32,
+    lines: Grid<char>,
 }
 
 impl Day4 {
     pub fn new() -> Self {
-        let lines: Vec<Vec<char>> = fs::read_to_string("data/year2024/day4")
-            .expect("cannot read file")
-            .split('\n')
-            .map(|line| line.chars().collect())
-            .collect();
-        let x_lim = (&lines)[0].len() as i32;
-        let y_lim = (&lines).len() as i32;
+        parse::load("data/year2024/day4", Self::parse)
+    }
 
-        Day4 { lines, x_lim, y_lim }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = Grid::parse(input, input, "XMAS")?;
+        Ok(Day4 { lines })
     }
 
-    pub fn search(&self, x: i32, y: i32, step_x: i32, step_y: i32, haystack: &str) -> u64 {
-        let mut start_x = 0;
-        let mut start_y = 0;
-        for c in haystack.chars() {
-            let y_pos = y + start_y;
-            let x_pos = x + start_x;
-            if y_pos < 0 {
-                return 0;
-            }
-            if y_pos >= self.y_lim {
-                return 0;
-            }
-            if x_pos < 0 {
-                return 0;
-            }
-            if x_pos >= self.x_lim {
-                return 0;
-            }
-            if (&self.lines)[y_pos as usize][x_pos  as usize] != c {
-                return 0;
-            }
-            start_x += step_x;
-            start_y += step_y;
-        }
-        
-        1
+    pub fn search(&self, start: Point, step: Point, haystack: &str) -> u64 {
+        let found = haystack.chars().zip(0..).all(|(c, i)| {
+            self.lines.get(start + step * i) == Some(&c)
+        });
+        found as u64
     }
 }
 
 impl Day for Day4 {
-    fn part_1(&mut self) -> u64 {
-        let mut count = 0;
+    fn part_1(&self) -> Answer {
         let haystack = "XMAS";
-        for y_i in 0..(&self.lines).len() {
-            for x_i in 0..(&self.lines)[y_i].len() {
-                let x = x_i as i32;
-                let y = y_i as i32;
-                let total = self.search(x, y, 1, 0, haystack) +
-                self.search(x, y, -1, 0, haystack) +
-                self.search(x, y, 0, 1, haystack) +
-                self.search(x, y, 0, -1, haystack) + 
-                self.search(x, y, 1, 1, haystack) +
-                self.search(x, y, 1, -1, haystack) +
-                self.search(x, y, -1, 1, haystack) +
-                self.search(x, y, -1, -1, haystack);
-                count += total;
-            }
-        }
-        count
+        let count: u64 = self
+            .lines
+            .positions()
+            .map(|pos| {
+                ALL_AROUND
+                    .iter()
+                    .map(|&step| self.search(pos, step, haystack))
+                    .sum::<u64>()
+            })
+            .sum();
+        count.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut count = 0;
-        for y_i in 0..(&self.lines).len() {
-            for x_i in 0..(&self.lines)[y_i].len() {
-                let x = x_i as i32;
-                let y = y_i as i32;
-                if (self.search(x, y, 1, 1, "MAS") == 1 || self.search(x, y, 1, 1, "SAM") == 1) 
-                && (self.search(x, y+2, 1, -1, "MAS") == 1 || self.search(x, y+2, 1, -1, "SAM") == 1) {
-                    count += 1;
-                }
+        let (down, up) = (Point::new(1, 1), Point::new(1, -1));
+        for pos in self.lines.positions() {
+            let below = pos.offset(0, 2);
+            if (self.search(pos, down, "MAS") == 1 || self.search(pos, down, "SAM") == 1)
+            && (self.search(below, up, "MAS") == 1 || self.search(below, up, "SAM") == 1) {
+                count += 1;
             }
         }
-        count
+        count.into()
     }
 }
@@ -1,6 +1,8 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 use rayon::prelude::*;
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day5 {
@@ -10,31 +12,29 @@ pub struct Day5 {
 
 impl Day5 {
     pub fn new() -> Self {
+        parse::load("data/year2024/day5", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
         let mut rules = HashMap::new();
-        let parts: Vec<String> = fs::read_to_string("data/year2024/day5")
-            .expect("Cannot read data file")
-            .split("\n\n")
-            .map(String::from)
-            .collect();
+        let (rules_str, pages_str) = parse::split_once(input, input, "\n\n")?;
 
-        parts[0].split('\n').for_each(|ln| {
-            let nums: Vec<u64> = ln
-                .split('|')
-                .map(|n| n.parse().expect("Not digit"))
-                .collect();
-            (*rules.entry(nums[0]).or_insert(vec![])).push(nums[1]);
-        });
+        for ln in rules_str.lines() {
+            let (before, after) = parse::split_once(input, ln, "|")?;
+            let before: u64 = parse::number(input, before)?;
+            (*rules.entry(before).or_insert(vec![])).push(parse::number(input, after)?);
+        }
 
-        let pages = parts[1]
-            .split('\n')
+        let pages = pages_str
+            .lines()
             .map(|ln| {
                 ln.split(',')
-                    .map(|n| n.parse().expect("Not digit"))
+                    .map(|n| parse::number(input, n))
                     .collect()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Day5 { rules, pages }
+        Ok(Day5 { rules, pages })
     }
 
     pub fn is_valid(&self, i: usize) -> bool {
@@ -99,7 +99,7 @@ impl Day5 {
 }
 
 impl Day for Day5 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut sum = 0;
 
         for (i, pg) in self.pages.iter().enumerate() {
@@ -107,14 +107,14 @@ impl Day for Day5 {
                 sum += pg[pg.len() / 2];
             }
         }
-        sum
+        sum.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let rules = &self.rules;
         self.pages
-            .par_iter_mut()
-            .map(|page| Day5::make_valid_page(rules, page))
-            .sum()
+            .par_iter()
+            .map(|page| Day5::make_valid_page(rules, &mut page.clone()))
+            .sum::<u64>().into()
     }
 }
@@ -1,7 +1,8 @@
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::fs;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day7 {
@@ -10,21 +11,24 @@ pub struct Day7 {
 
 impl Day7 {
     pub fn new() -> Self {
-        let lines = fs::read_to_string("data/year2024/day7")
-            .expect("Cannot read data file")
-            .split('\n')
+        parse::load("data/year2024/day7", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
             .map(|l| {
-                let parts: Vec<&str> = l.split(": ").collect();
-                let res: u64 = parts[0].parse().unwrap();
-                let vals: Vec<u64> = (&parts)[1]
+                let (res, vals) = parse::split_once(input, l, ": ")?;
+                let res: u64 = parse::number(input, res)?;
+                let vals: Vec<u64> = vals
                     .split_ascii_whitespace()
-                    .map(|c| c.parse().unwrap())
-                    .collect();
-                (res, vals)
+                    .map(|c| parse::number(input, c))
+                    .collect::<Result<_, _>>()?;
+                Ok((res, vals))
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
 
-        Day7 { lines }
+        Ok(Day7 { lines })
     }
 }
 
@@ -98,23 +102,23 @@ fn check_if_any_combination_with_concat(res: u64, series: &[u64]) -> bool {
 }
 
 impl Day for Day7 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.lines.par_iter().filter_map(|(res, series)| {
             if check_if_any_combination(*res, series) {
                 Some(res)
             } else {
                 None
             }
-        }).sum()
+        }).sum::<u64>().into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         self.lines.par_iter().filter_map(|(res, series)| {
             if check_if_any_combination_with_concat(*res, series) {
                 Some(res)
             } else {
                 None
             }
-        }).sum()
+        }).sum::<u64>().into()
     }
 }
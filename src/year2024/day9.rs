@@ -1,5 +1,6 @@
-use std::fs;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
 
 pub struct Day9 {
@@ -8,13 +9,24 @@ pub struct Day9 {
 
 impl Day9 {
     pub fn new() -> Self {
-        let input = fs::read_to_string("data/year2024/day9")
-            .expect("cannot read data file")
-            .chars()
-            .map(|c| c.to_digit(10).expect("Not a digit") as u8)
-            .collect();
+        parse::load("data/year2024/day9", Self::parse)
+    }
 
-        Day9 { input }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let disk = input.trim_end();
+        if disk.is_empty() {
+            return Err(ParseError::at(input, disk, "a disk map"));
+        }
+        let input = disk
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| ParseError::at(input, &disk[i..i + c.len_utf8()], "a digit"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Day9 { input })
     }
 }
 
@@ -30,7 +42,7 @@ fn find_multiply_sum(start_i: usize, end_i: usize, digit2: usize) -> u64 {
 }
 
 impl Day for Day9 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut i = 0;
         let mut j = self.input.len() - 1;
         let mut input = self.input.clone();
@@ -83,47 +95,66 @@ impl Day for Day9 {
         }
 
         //println!("i={}, j={} Final: {:?}", i, j, self.input);
-        res as u64
+        res.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut i = 0;
         let mut res = 0;
         let mut idx_sum = 0;
-        let input_cp = self.input.clone();
+        let mut input = self.input.clone();
 
-        while i < self.input.len() {
+        while i < input.len() {
             if i % 2 == 0 {
-                if self.input[i] != 0 {
-                    res += find_multiply_sum(idx_sum, self.input[i] as usize, i);
+                if input[i] != 0 {
+                    res += find_multiply_sum(idx_sum, input[i] as usize, i);
                 }
-                idx_sum += input_cp[i] as usize;
+                idx_sum += self.input[i] as usize;
                 i += 1;
-                // println!("i={}, Filled Space: {:?}", i, self.input);
+                // println!("i={}, Filled Space: {:?}", i, input);
                 continue;
             }
 
             // handle free space
-            let mut j = self.input.len() - 1;
+            let mut j = input.len() - 1;
             if j % 2 == 1 {
                 j -= 1;
             }
             // find block that fits
             
-            while j > i && self.input[i] > 0 {
-                if self.input[j] <= self.input[i] && self.input[j] != 0 {
-                    res += find_multiply_sum(idx_sum, self.input[j] as usize, j);
-                    idx_sum += input_cp[j] as usize;
-                    self.input[i] -= self.input[j];
-                    self.input[j] = 0;
-                    // println!("i={}, j={}, Empty Space: {:?}", i, j, self.input);
+            while j > i && input[i] > 0 {
+                if input[j] <= input[i] && input[j] != 0 {
+                    res += find_multiply_sum(idx_sum, input[j] as usize, j);
+                    idx_sum += self.input[j] as usize;
+                    input[i] -= input[j];
+                    input[j] = 0;
+                    // println!("i={}, j={}, Empty Space: {:?}", i, j, input);
                 }
                 j -= 2;
             }
-            idx_sum += self.input[i] as usize;
+            idx_sum += input[i] as usize;
             i += 1;
         }
 
-        res as u64
+        res.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2024::day9::Day9;
+
+    #[test]
+    fn test_sample() {
+        let day = Day9::parse("2333133121414131402\n").unwrap();
+        assert_eq!(day.part_1(), 1928u64.into());
+        assert_eq!(day.part_2(), 2858u64.into());
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(Day9::parse("").is_err());
+        assert_eq!(Day9::parse(" \n").err().unwrap().expected, "a disk map");
     }
 }
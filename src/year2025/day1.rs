@@ -1,5 +1,6 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day1 {
     data: Vec<(bool, i32)>,
@@ -7,23 +8,27 @@ pub struct Day1 {
 
 impl Day1 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day1")
-            .expect("Data file cannot be read")
+        parse::load("data/year2025/day1", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = input
             .lines()
-            .enumerate()
-            .map(|(n, line)| {
-                let dir = line.chars().next().expect("Char not found");
-                let num_str = &line[1..];
-                (dir == 'L', i32::from_str_radix(num_str, 10).expect(&format!("Line {} cannot be read", n + 1)))
+            .map(|line| {
+                let (dir, num_str) = match line.strip_prefix('L') {
+                    Some(num_str) => ('L', num_str),
+                    None => ('R', parse::expect(input, line.strip_prefix('R'), line, "a rotation like `L68` or `R48`")?),
+                };
+                Ok((dir == 'L', parse::number(input, num_str)?))
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
 
-        Day1 { data }
+        Ok(Day1 { data })
     }
 }
 
 impl Day for Day1 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut res: i32 = 50;
         let mut count = 0;
         self.data.iter().for_each(|(l, num)| {
@@ -43,10 +48,10 @@ impl Day for Day1 {
                 count = count + 1;
             }
         });
-        count
+        count.into()
     }
 
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut res: i32 = 50;
         let mut count = 0;
         self.data.iter().for_each(|(l, num)| {
@@ -70,6 +75,6 @@ impl Day for Day1 {
                 }
             }
         });
-        count
+        count.into()
     }
 }
@@ -1,29 +1,80 @@
 use ahash::{AHashMap, AHashSet};
 use rayon::prelude::*;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::{fs, u16};
+use std::u16;
+
+/// One line of the manual: the lights to turn on, the lights each button toggles, and the
+/// joltage each counter needs.
+struct Machine {
+    lights: AHashSet<u16>,
+    buttons: Vec<Vec<u16>>,
+    joltages: Vec<u16>,
+}
 
 pub struct Day10 {
-    lines: Vec<String>,
+    machines: Vec<Machine>,
 }
 
 impl Day10 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day10")
-            .expect("Cannot read data")
+        parse::load("data/year2025/day10", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let machines = input
             .lines()
-            .map(|line| String::from(line))
-            .collect();
-        Day10 { lines: data }
+            .map(|line| Day10::parse_machine(input, line))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Day10 { machines })
+    }
+
+    fn parse_machine(input: &str, line: &str) -> Result<Machine, ParseError> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let (&diagram, rest) = parse::expect(input, parts.split_first(), line, "a light diagram")?;
+        let (&joltages, buttons) = parse::expect(input, rest.split_last(), line, "joltages like `{3,5,4,7}`")?;
+
+        let lights = diagram
+            .strip_prefix('[')
+            .and_then(|d| d.strip_suffix(']'))
+            .filter(|d| d.chars().all(|c| c == '.' || c == '#'))
+            .ok_or_else(|| ParseError::at(input, diagram, "a light diagram like `[.##.]`"))?;
+        let count = lights.len();
+        let lights = parse_diagram_to_config(lights);
+        let joltages = parse_list(input, joltages, '{', '}')?;
+        if joltages.len() != count {
+            return Err(ParseError::at(input, line, format!("{count} joltages")));
+        }
+
+        let buttons = buttons
+            .iter()
+            .map(|button| {
+                let lights = parse_list(input, button, '(', ')')?;
+                match lights.iter().position(|&light| light as usize >= count) {
+                    Some(_) => Err(ParseError::at(input, button, format!("lights below {count}"))),
+                    None => Ok(lights),
+                }
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Machine { lights, buttons, joltages })
     }
 }
 
+/// The comma separated numbers of `token` between `open` and `close`.
+fn parse_list(input: &str, token: &str, open: char, close: char) -> Result<Vec<u16>, ParseError> {
+    let inner = token
+        .strip_prefix(open)
+        .and_then(|t| t.strip_suffix(close))
+        .ok_or_else(|| ParseError::at(input, token, format!("numbers between `{open}` and `{close}`")))?;
+    inner.split(',').map(|n| parse::number(input, n)).collect()
+}
+
 fn parse_diagram_to_config(d: &str) -> AHashSet<u16> {
     let mut res = AHashSet::new();
-    let char_arr: Vec<char> = d.chars().collect();
-    for (i, c) in (&char_arr[1..char_arr.len() - 1]).iter().enumerate() {
-        if *c == '#' {
+    for (i, c) in d.chars().enumerate() {
+        if c == '#' {
             res.insert(i as u16);
         }
     }
@@ -235,44 +286,23 @@ fn tree_search_3(config: &mut Vec<u16>, buttons: &mut Vec<(Vec<u32>, bool)>, dep
     }
 }
 
-fn parse_joltage(input_str: &str) -> Vec<u16> {
-    let mut result = vec![];
-    let input = &input_str[1..input_str.len() - 1];
-    for p in input.split(',') {
-        result.push(u16::from_str_radix(p, 10).expect("Cannot parse"))
-    }
-    result
-}
-
 impl Day for Day10 {
-    fn part_1(&mut self) -> u64 {
-        let data: Vec<(AHashSet<u16>, Vec<AHashSet<u16>>)> = self
-            .lines
+    fn part_1(&self) -> Answer {
+        let data: Vec<(&AHashSet<u16>, Vec<AHashSet<u16>>)> = self
+            .machines
             .iter()
-            .map(|line| {
-                let mut parts = line.split(' ');
-                let mut buttons = vec![];
-                let first = parts.next().expect("first part not found");
-                let final_config = parse_diagram_to_config(first);
-                for button in parts.rev().skip(1) {
-                    let button = &button[1..button.len() - 1];
-                    let mut b = AHashSet::new();
-                    for digit in button.split(',') {
-                        b.insert(u16::from_str_radix(digit, 10).expect("Cannot parse"));
-                    }
-                    buttons.push(b);
-                }
-                buttons.reverse();
-                (final_config, buttons)
+            .map(|machine| {
+                let buttons = machine.buttons.iter().map(|b| b.iter().copied().collect()).collect();
+                (&machine.lights, buttons)
             })
             .collect();
 
-        let solution = data
+        let solution: u64 = data
             .par_iter()
             .map(|(config, buttons)| {
                 let current_state: AHashSet<u16> = AHashSet::default();
                 let mut cache = AHashMap::default();
-                let res = tree_search(&config, &buttons, &current_state, 0, -1, &mut cache);
+                let res = tree_search(config, &buttons, &current_state, 0, -1, &mut cache);
                 if res == -1 {
                     panic!("Cannot find solution for {config:?}");
                 }
@@ -285,33 +315,21 @@ impl Day for Day10 {
                 res as u64
             })
             .sum();
-        solution
+        solution.into()
     }
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut data: Vec<(Vec<u16>, Vec<Vec<u32>>)> = self
-            .lines
+            .machines
             .iter()
-            .map(|line| {
-                let mut parts = line.split(' ').rev();
-                let mut buttons = vec![];
-                let first = parts.next().expect("first part not found");
-                let final_config = parse_joltage(first);
-                for button in parts.rev().skip(1) {
-                    let button = &button[1..button.len() - 1];
-                    let mut b = vec![];
-                    for digit in button.split(',') {
-                        b.push(u32::from_str_radix(digit, 10).expect("Cannot parse"));
-                    }
-                    buttons.push(b);
-                }
-                // buttons.reverse();
+            .map(|machine| {
+                let mut buttons: Vec<Vec<u32>> = machine.buttons.iter().map(|b| b.iter().map(|&l| l as u32).collect()).collect();
                 buttons.sort_unstable_by(|a, b| b.len().cmp(&a.len()));
-                (final_config, buttons)
+                (machine.joltages.clone(), buttons)
             })
             .collect();
         
 
-        let solution = data
+        let solution: u64 = data
             .par_iter_mut()
             .map(|(config, buttons_plain)| {
                 let mut buttons = buttons_plain
@@ -323,7 +341,7 @@ impl Day for Day10 {
                 if res == u64::MAX {
                     panic!("Result not found");
                 }
-                println!("Config: {config:?};; Result = {res}");
+                eprintln!("Config: {config:?};; Result = {res}");
                 // print!("Buttons: ");
                 // for button in buttons {
                 //     print!("{button:?}, ");
@@ -334,6 +352,34 @@ impl Day for Day10 {
             })
             .sum();
         // let solution = 0;
-        solution
+        solution.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::Day;
+    use crate::year2025::day10::Day10;
+
+    const SAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    #[test]
+    fn test_sample() {
+        let day = Day10::parse(SAMPLE).unwrap();
+        assert_eq!(day.part_1(), 7u64.into());
+        assert_eq!(day.part_2(), 33u64.into());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day10::parse("[.##.] (x,3) {3,5,4,7}\n").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (9, "x"));
+        assert!(Day10::parse("[.##.] (4) {3,5,4,7}\n").is_err());
+        assert!(Day10::parse("[.#x.] (3) {3,5,4,7}\n").is_err());
+        assert!(Day10::parse("[.##.] (3) {3,5}\n").is_err());
+        assert!(Day10::parse("[.##.]\n").is_err());
     }
 }
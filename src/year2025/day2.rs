@@ -1,7 +1,8 @@
 use rayon::prelude::*;
 
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day2 {
     data: Vec<(u64, u64)>,
@@ -9,26 +10,19 @@ pub struct Day2 {
 
 impl Day2 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day2")
-            .expect("Cannot read data")
-            .lines()
-            .map(|line| {
-                line.split(",")
-                    .map(|ids| {
-                        let mut parts = ids.split("-");
-                        let first =
-                            u64::from_str_radix(parts.next().expect("first id not found"), 10)
-                                .expect("Cannot parse");
-                        let last =
-                            u64::from_str_radix(parts.next().expect("second id not found"), 10)
-                                .expect("cannot parse");
-                        (first, last)
-                    })
-                    .collect::<Vec<(u64, u64)>>()
+        parse::load("data/year2025/day2", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = input
+            .trim_end()
+            .split(",")
+            .map(|ids| {
+                let (first, last) = parse::split_once(input, ids, "-")?;
+                Ok((parse::number(input, first)?, parse::number(input, last)?))
             })
-            .next()
-            .expect("No data");
-        Day2 { data }
+            .collect::<Result<Vec<(u64, u64)>, ParseError>>()?;
+        Ok(Day2 { data })
     }
 }
 
@@ -71,7 +65,7 @@ fn is_invalid_2(num: &u64) -> bool {
 }
 
 impl Day for Day2 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         self.data.par_iter().map(|(a, b)| {
             let mut sp = 0;
             for i in *a..(*b + 1) {
@@ -80,9 +74,9 @@ impl Day for Day2 {
                 }
             }
             sp
-        }).sum()
+        }).sum::<u64>().into()
     }
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         self.data.par_iter().map(|(a, b)| {
             let mut sp = 0;
             for i in *a..(*b + 1) {
@@ -91,6 +85,6 @@ impl Day for Day2 {
                 }
             }
             sp
-        }).sum()
+        }).sum::<u64>().into()
     }
 }
@@ -1,5 +1,6 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day3 {
     data: Vec<Vec<u32>>,
@@ -7,12 +8,15 @@ pub struct Day3 {
 
 impl Day3 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day3")
-            .expect("Cannot read data")
-            .lines()
+        parse::load("data/year2025/day3", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = parse::grid(input, input, "123456789")?
+            .into_iter()
             .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
             .collect();
-        Day3 { data }
+        Ok(Day3 { data })
     }
 }
 
@@ -30,7 +34,7 @@ fn largest_digit(line: &Vec<u32>, start: usize, end: usize) -> usize {
 }
 
 impl Day for Day3 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut res = 0;
         for line in &self.data {
             let l = line.len();
@@ -40,9 +44,9 @@ impl Day for Day3 {
             res += num;
         }
 
-        res as u64
+        res.into()
     }
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut res = 0;
         for line in &self.data {
             let mut digits: Vec<u32> = vec![];
@@ -61,6 +65,6 @@ impl Day for Day3 {
             // dbg!(num);
         }
 
-        res as u64
+        res.into()
     }
 }
@@ -1,121 +1,49 @@
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day4 {
-    data: Vec<Vec<char>>,
+    data: Grid<char>,
 }
 
 impl Day4 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day4")
-            .expect("Cannot read data")
-            .lines()
-            .map(|line| line.chars().collect())
-            .collect();
-        Day4 { data }
+        parse::load("data/year2025/day4", Self::parse)
     }
-}
-
-impl Day for Day4 {
-    fn part_1(&mut self) -> u64 {
-        let mut count = 0;
-        let y_lim = self.data.len();
-        let x_lim = self.data[0].len();
-        self.data.iter().enumerate().for_each(|(y, row)| {
-            row.iter().enumerate().for_each(|(x, val)| {
-                if *val != '@' {
-                    return;
-                }
-                let mut ct = 0;
-                // top row
-                if y > 0 && x > 0 && self.data[y - 1][x - 1] == '@' {
-                    ct += 1;
-                }
-                if y > 0 && self.data[y - 1][x] == '@' {
-                    ct += 1;
-                }
-                if y > 0 && x < x_lim - 1 && self.data[y - 1][x + 1] == '@' {
-                    ct += 1;
-                }
 
-                // bottom row
-                if y < y_lim - 1 && x > 0 && self.data[y + 1][x - 1] == '@' {
-                    ct += 1;
-                }
-                if y < y_lim - 1 && self.data[y + 1][x] == '@' {
-                    ct += 1;
-                }
-                if y < y_lim - 1 && x < x_lim - 1 && self.data[y + 1][x + 1] == '@' {
-                    ct += 1;
-                }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = Grid::parse(input, input, ".@")?;
+        Ok(Day4 { data })
+    }
+}
 
-                // same row
-                if x > 0 && self.data[y][x - 1] == '@' {
-                    ct += 1;
-                }
-                if x < x_lim - 1 && self.data[y][x + 1] == '@' {
-                    ct += 1;
-                }
+/// Whether the roll at `pos` has fewer than four rolls around it.
+fn accessible(data: &Grid<char>, pos: Point) -> bool {
+    data.neighbours8(pos).filter(|&p| data[p] == '@').count() < 4
+}
 
-                if ct < 4 {
-                    count += 1;
-                }
-            });
-        });
-        count
+impl Day for Day4 {
+    fn part_1(&self) -> Answer {
+        let count = self
+            .data
+            .find_all(&'@')
+            .filter(|&pos| accessible(&self.data, pos))
+            .count();
+        count.into()
     }
-    fn part_2(&mut self) -> u64 {
-        let mut count = 0;
+    fn part_2(&self) -> Answer {
+        let mut data = self.data.clone();
         let mut count_total = 0;
-        let y_lim = self.data.len();
-        let x_lim = self.data[0].len();
         loop {
-            let mut i_rem: Vec<(usize, usize)> = vec![];
-
-            self.data.iter().enumerate().for_each(|(y, row)| {
-                row.iter().enumerate().for_each(|(x, val)| {
-                    if *val != '@' {
-                        return;
-                    }
-                    let mut ct = 0;
-                    // top row
-                    if y > 0 && x > 0 && self.data[y - 1][x - 1] == '@' {
-                        ct += 1;
-                    }
-                    if y > 0 && self.data[y - 1][x] == '@' {
-                        ct += 1;
-                    }
-                    if y > 0 && x < x_lim - 1 && self.data[y - 1][x + 1] == '@' {
-                        ct += 1;
-                    }
-
-                    // bottom row
-                    if y < y_lim - 1 && x > 0 && self.data[y + 1][x - 1] == '@' {
-                        ct += 1;
-                    }
-                    if y < y_lim - 1 && self.data[y + 1][x] == '@' {
-                        ct += 1;
-                    }
-                    if y < y_lim - 1 && x < x_lim - 1 && self.data[y + 1][x + 1] == '@' {
-                        ct += 1;
-                    }
-
-                    // same row
-                    if x > 0 && self.data[y][x - 1] == '@' {
-                        ct += 1;
-                    }
-                    if x < x_lim - 1 && self.data[y][x + 1] == '@' {
-                        ct += 1;
-                    }
-
-                    if ct < 4 {
-                        count += 1;
-                        i_rem.push((x, y))
-                    }
-                });
-            });
-            for (x, y) in i_rem {
-                self.data[y][x] = '.';
+            let i_rem: Vec<Point> = data
+                .find_all(&'@')
+                .filter(|&pos| accessible(&data, pos))
+                .collect();
+            let count = i_rem.len();
+            for pos in i_rem {
+                data[pos] = '.';
             }
 
             if count == 0 {
@@ -123,8 +51,7 @@ impl Day for Day4 {
             }
             count_total += count;
             dbg!(count);
-            count = 0;
         }
-        count_total
+        count_total.into()
     }
 }
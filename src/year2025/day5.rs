@@ -1,74 +1,40 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::ranges::{InclusiveInterval, IntervalSet};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day5 {
-    ranges: Vec<(u64, u64)>,
-    ids: Vec<u64>
+    fresh: IntervalSet,
+    ids: Vec<i64>
 }
 
 impl Day5 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day5")
-            .expect("Cannot read data");
-        let mut parts = data.split("\n\n");
-        let ranges = parts.next().unwrap().split("\n").map(|line| {
-            let mut pts = line.split("-");
-            let start = u64::from_str_radix(pts.next().unwrap(), 10).unwrap();
-            let end = u64::from_str_radix(pts.next().unwrap(), 10).unwrap();
-            (start, end)
-        }).collect();
+        parse::load("data/year2025/day5", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let (ranges, ids) = parse::split_once(input, input, "\n\n")?;
+        let ranges = ranges.lines().map(|line| {
+            let (start, end) = parse::split_once(input, line, "-")?;
+            Ok(InclusiveInterval::new(parse::number(input, start)?, parse::number(input, end)?))
+        }).collect::<Result<Vec<_>, ParseError>>()?;
+        let fresh = ranges.into_iter().collect();
 
-        let ids = parts.next().unwrap().split("\n").map(|line| {
-            u64::from_str_radix(line, 10).unwrap()
-        }).collect();
+        let ids = ids.lines().map(|line| {
+            parse::number(input, line)
+        }).collect::<Result<_, _>>()?;
 
-        Day5 { ranges, ids }
+        Ok(Day5 { fresh, ids })
     }
 }
 
 impl Day for Day5 {
-    fn part_1(&mut self) -> u64 {
-        let mut count = 0;
-        for id in &self.ids {
-            for (st, ed) in &self.ranges {
-                if id >= st && id <= ed {
-                    count += 1;
-                    // dbg!(id);
-                    break;
-                }
-            }
-        }
-        count
+    fn part_1(&self) -> Answer {
+        self.ids.iter().filter(|&&id| self.fresh.contains(id)).count().into()
     }
-    fn part_2(&mut self) -> u64 {
-        self.ranges.sort_by(|a, b| {
-           a.0.cmp(&b.0)
-        });
-        let l = self.ranges.len();
-        let mut j = 1;
 
-        let mut cmb_ranges = vec![];
-        let mut current_range = self.ranges[0];
-        while j < l {
-            let b = self.ranges[j];
-            if b.0 > current_range.1 {
-                cmb_ranges.push(current_range);
-                current_range = b;
-                j+=1;
-                continue;
-            }
-            if b.1 <= current_range.1 {
-                j += 1;
-                continue;
-            }
-            current_range.1 = b.1;
-            j += 1;
-        }
-        cmb_ranges.push(current_range);
-        let mut sum = 0;
-        for rng in cmb_ranges {
-            sum += 1 + rng.1 - rng.0;
-        }
-        sum
+    fn part_2(&self) -> Answer {
+        self.fresh.len().into()
     }
 }
@@ -1,39 +1,41 @@
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::fs;
 
 pub struct Day6 {
     numbers: Vec<Vec<u64>>,
-    symbols: Vec<char>
+    symbols: Vec<char>,
+    data: String,
 }
 
 impl Day6 {
     pub fn new() -> Self {
-        let data =fs::read_to_string("data/year2025/day6")
-            .expect("Cannot read data"); 
-        let mut all_lines = data
+        parse::load("data/year2025/day6", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut all_lines = input
             .lines()
             .rev();
-        let last_line = all_lines.next().expect("last line not found");
-        let symbols = last_line.trim().split(" ")
-        .filter(|p| p.len() > 0)
-        .map(|p| p.chars().next().unwrap())
-        .filter(|p| *p != ' ')
-        .collect();
+        let last_line = parse::expect(input, all_lines.next(), input, "a line of operators")?;
+        let symbols = last_line.split_whitespace()
+        .map(|p| match p {
+            "*" | "+" => Ok(p.chars().next().unwrap()),
+            _ => Err(ParseError::at(input, p, "`*` or `+`")),
+        })
+        .collect::<Result<_, _>>()?;
         let numbers = all_lines
             .map(|line| {
-                line.
-                trim()
-                .replace("  ", " ")
-                .split(" ")
-                .filter(|n| n.trim().len() > 0)
-                .map(|num| u64::from_str_radix(num.trim(), 10).unwrap()).collect()
-            }).collect();
-        Day6 { numbers, symbols }
+                line
+                .split_whitespace()
+                .map(|num| parse::number(input, num)).collect()
+            }).collect::<Result<_, _>>()?;
+        Ok(Day6 { numbers, symbols, data: input.to_string() })
     }
 }
 
 impl Day for Day6 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut solution = vec![];
         for sym in &self.symbols { 
             if *sym == '*' {
@@ -51,11 +53,10 @@ impl Day for Day6 {
                 }
             }
         }
-        solution.iter().sum()
+        solution.iter().sum::<u64>().into()
     }
-    fn part_2(&mut self) -> u64 {
-        let data =fs::read_to_string("data/year2025/day6")
-            .expect("Cannot read data"); 
+    fn part_2(&self) -> Answer {
+        let data = &self.data;
         let mut symbols: Vec<char> = vec![];
         let mut column_width: Vec<usize> = vec![];
         let mut all_lines = data.lines().rev();
@@ -111,6 +112,6 @@ impl Day for Day6 {
             
         }
 
-        res
+        res.into()
     }
 }
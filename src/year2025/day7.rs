@@ -1,133 +1,109 @@
 use ahash::AHashMap;
 
+use crate::answer::Answer;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use std::{fs};
 
 pub struct Day7 {
-    data: Vec<Vec<char>>,
+    data: Grid<char>,
 }
 
 impl Day7 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day7")
-            .expect("Cannot read data")
-            .lines()
-            .map(|line| line.chars().collect())
-            .collect();
-        Day7 { data }
+        parse::load("data/year2025/day7", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = Grid::parse(input, input, ".S^")?;
+        Ok(Day7 { data })
     }
 }
 
+/// Columns the beam splits into around the splitter below `pos`, staying inside the manifold.
+fn split(data: &Grid<char>, pos: Point) -> (Point, Point) {
+    let below = pos.offset(0, 1);
+    let left = if pos.x == 0 { below } else { below.offset(-1, 0) };
+    let right = if pos.x as usize == data.width() - 1 { below } else { below.offset(1, 0) };
+    (left, right)
+}
 
-fn tick2(data: &mut Vec<Vec<char>>, x: usize, y: usize, cache: &mut AHashMap<(usize, usize), u64>) -> u64 {
-    let y_lim = data.len();
-    let x_lim = data[0].len();
-    if let Some(val) = cache.get(&(x, y)) {
+fn tick2(data: &mut Grid<char>, pos: Point, cache: &mut AHashMap<Point, u64>) -> u64 {
+    if let Some(val) = cache.get(&pos) {
         return *val;
     }
 
-    if y == y_lim - 1 {
+    if pos.y as usize == data.height() - 1 {
         return 1;
     }
-    if data[y+1][x] == '.' || data[y+1][x] == '|' {
-        data[y+1][x] = '|';
-        let res = tick2(data, x, y+1, cache);
-        cache.insert((x, y+1), res);
+    let below = pos.offset(0, 1);
+    if data[below] == '.' || data[below] == '|' {
+        data[below] = '|';
+        let res = tick2(data, below, cache);
+        cache.insert(below, res);
         return res;
     }
-    if data[y+1][x] == '^' {
-        let left = if x == 0 { x } else { x-1 };
-        let right = if x == x_lim - 1 { x } else { x+1 };
+    if data[below] == '^' {
+        let (left, right) = split(data, pos);
         let mut a = 0;
         let mut b = 0;
-        if data[y+1][left] != '^' {
-            data[y+1][left] = '|';
-            a = tick2(data,left, y+1, cache);
-            cache.insert((left, y+1), a);
+        if data[left] != '^' {
+            data[left] = '|';
+            a = tick2(data, left, cache);
+            cache.insert(left, a);
         }
-        if data[y+1][right] != '^' {
-            data[y+1][right] = '|';
-            b = tick2(data, right, y+1, cache);
-            cache.insert((right, y+1), b);
+        if data[right] != '^' {
+            data[right] = '|';
+            b = tick2(data, right, cache);
+            cache.insert(right, b);
         }
         return a + b;
     }
     return 0;
 }
 
-fn tick(data: &mut Vec<Vec<char>>, x: usize, y: usize, count: &mut u64) {
-    let y_lim = data.len();
-    let x_lim = data[0].len();
-
-    if y == y_lim - 1 {
+fn tick(data: &mut Grid<char>, pos: Point, count: &mut u64) {
+    if pos.y as usize == data.height() - 1 {
         return;
     }
-    if data[y+1][x] == '.' {
-        data[y+1][x] = '|';
-        return tick(data, x, y+1, count);
+    let below = pos.offset(0, 1);
+    if data[below] == '.' {
+        data[below] = '|';
+        return tick(data, below, count);
     }
-    if data[y+1][x] == '^' {
-        let left = if x == 0 { x } else { x-1 };
-        let right = if x == x_lim - 1 { x } else { x+1 };
-        if data[y+1][left] != '^' {
-            data[y+1][left] = '|';
+    if data[below] == '^' {
+        let (left, right) = split(data, pos);
+        if data[left] != '^' {
+            data[left] = '|';
         }
-        if data[y+1][right] != '^' {
-            data[y+1][right] = '|';
+        if data[right] != '^' {
+            data[right] = '|';
         }
         *count += 1;
-        tick(data,left, y+1, count);
-        tick(data, right, y+1, count);
+        tick(data, left, count);
+        tick(data, right, count);
     }
 }
 
 impl Day for Day7 {
-    fn part_1(&mut self) -> u64 {
-        let mut start = (0, 0);
-        for (y, line) in self.data.iter().enumerate() {
-            let mut found = false;
-            for (x, ch) in line.iter().enumerate() {
-                if *ch == 'S' {
-                    start = (x, y);
-                    found = true;
-                    break;
-                }
-            }
-            if found {
-                break;
-            }
-        }
+    fn part_1(&self) -> Answer {
+        let start = self.data.find(&'S').unwrap_or_default();
         let mut count = 0;
-        self.data[start.1+1][start.0] = '|';
-        tick(&mut self.data, start.0, start.1 + 1, &mut count);
+        let mut data = self.data.clone();
+        data[start.offset(0, 1)] = '|';
+        tick(&mut data, start.offset(0, 1), &mut count);
 
-        // for lin in &self.data {
-        //     for ch in lin {
-        //         print!("{ch}");
-        //     }
-        //     println!();
-        // }
+        // eprintln!("{data}");
 
-        count
+        count.into()
     }
-    fn part_2(&mut self) -> u64 {
-        let mut start = (0, 0);
-        for (y, line) in self.data.iter().enumerate() {
-            let mut found = false;
-            for (x, ch) in line.iter().enumerate() {
-                if *ch == 'S' {
-                    start = (x, y);
-                    found = true;
-                    break;
-                }
-            }
-            if found {
-                break;
-            }
-        }
-        self.data[start.1+1][start.0] = '|';
-        let mut cache: AHashMap<(usize, usize), u64> = AHashMap::new();
-        return tick2(&mut self.data, start.0, start.1 + 1, &mut cache);
+    fn part_2(&self) -> Answer {
+        let start = self.data.find(&'S').unwrap_or_default();
+        let mut data = self.data.clone();
+        data[start.offset(0, 1)] = '|';
+        let mut cache: AHashMap<Point, u64> = AHashMap::new();
+        return tick2(&mut data, start.offset(0, 1), &mut cache).into();
         
     }
 }
@@ -1,43 +1,13 @@
 use ahash::AHashSet;
 
+use crate::answer::Answer;
+use crate::geom::Point3;
+use crate::parse::{self, ParseError};
 use crate::traits::Day;
-use core::fmt;
-use std::fs;
 use std::collections::BinaryHeap;
 
-#[derive(Hash, Clone, Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-    z: i64
-}
-
-impl Point {
-    fn distance(&self, other: &Point) -> i64 {
-        let p1 = self.x - other.x;
-        let p2 = self.y - other.y;
-        let p3 = self.z - other.z;
-        let d = p1 * p1 + p2 * p2 + p3 * p3;
-        d
-    }
-}
-
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
-    }
-}
-
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
-    }
-}
-
-impl Eq for Point { }
-
 pub struct Day8 {
-    data: Vec<Point>,
+    data: Vec<Point3>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -61,45 +31,49 @@ impl PartialOrd for Node {
 
 impl Day8 {
     pub fn new() -> Self {
-        let data = fs::read_to_string("data/year2025/day8")
-            .expect("String not found")
+        parse::load("data/year2025/day8", Self::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = input
             .lines()
             .map(|line| {
-                let mut parts = line.split(",");
-                let x = i64::from_str_radix(parts.next().unwrap(), 10).unwrap();
-                let y = i64::from_str_radix(parts.next().unwrap(), 10).unwrap();
-                let z = i64::from_str_radix(parts.next().unwrap(), 10).unwrap();
-                Point { x, y , z }
+                let (x, rest) = parse::split_once(input, line, ",")?;
+                let (y, z) = parse::split_once(input, rest, ",")?;
+                let x = parse::number(input, x)?;
+                let y = parse::number(input, y)?;
+                let z = parse::number(input, z)?;
+                Ok(Point3::new(x, y, z))
             })
-            .collect();
-        Day8 { data }
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Day8 { data })
     }
 }
 
 #[allow(dead_code)]
-fn print_cluster(clusters: &Vec<AHashSet<Point>>) {
+fn print_cluster(clusters: &Vec<AHashSet<Point3>>) {
     for (i, c) in clusters.iter().enumerate() {
-        println!("Cluster {i}");
+        eprintln!("Cluster {i}");
         for p in c {
-            println!("{p}");
+            eprintln!("{p}");
         }
-        println!();
+        eprintln!();
     }
 }
 
 
 impl Day for Day8 {
-    fn part_1(&mut self) -> u64 {
+    fn part_1(&self) -> Answer {
         let mut heap: BinaryHeap<Node> = BinaryHeap::new();
         
         for i in 0..self.data.len()-1 {
             for j in i+1..self.data.len() {
-                let d = self.data[i].distance(&self.data[j]);
+                let d = self.data[i].distance_squared(self.data[j]);
                 heap.push(Node { pair: (i, j), d });
             }
         }
 
-        let mut clusters: Vec<AHashSet<Point>> = vec![];
+        let mut clusters: Vec<AHashSet<Point3>> = vec![];
         let mut count = 0;
         for node in heap.into_sorted_vec() {
             if count == 1000 {
@@ -125,23 +99,23 @@ impl Day for Day8 {
             }
             if merge_idx.len() == 0 {
                 let mut cls = AHashSet::new();
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
                 clusters.push(cls);
                 count += 1;
             }
             else if merge_idx.len() == 1 {
                 let cls = &mut clusters[merge_idx[0]];
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
                 count += 1;
             } else {
                 let mut cls = AHashSet::new();
                 for &idx in &merge_idx {
                     cls.extend(clusters[idx].iter().cloned());
                 }
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
                 let mut new_cluster = vec![];
                 new_cluster.push(cls);
                 for (i, c) in clusters.into_iter().enumerate() {
@@ -157,28 +131,28 @@ impl Day for Day8 {
             // println!("\n\n");
         }
         clusters.sort_by(|a, b| b.len().cmp(&a.len()));
-        println!("Total clusters: {}", clusters.len());
+        eprintln!("Total clusters: {}", clusters.len());
         let a = clusters[0].len() as u64;
         let b = clusters[1].len() as u64;
         let c = clusters[2].len() as u64;
 
-        println!("{}, {}, {}", a, b, c);
-        a * b * c
+        eprintln!("{}, {}, {}", a, b, c);
+        (a * b * c).into()
     }
-    fn part_2(&mut self) -> u64 {
+    fn part_2(&self) -> Answer {
         let mut heap: BinaryHeap<Node> = BinaryHeap::new();
         
         for i in 0..self.data.len()-1 {
             for j in i+1..self.data.len() {
-                let d = self.data[i].distance(&self.data[j]);
+                let d = self.data[i].distance_squared(self.data[j]);
                 heap.push(Node { pair: (i, j), d });
             }
         }
 
-        let mut clusters: Vec<AHashSet<Point>> = vec![];
+        let mut clusters: Vec<AHashSet<Point3>> = vec![];
         for point in &self.data {
             let mut st = AHashSet::new();
-            st.insert(point.clone());
+            st.insert(*point);
             clusters.push(st);
         }
         for node in heap.into_sorted_vec() {
@@ -200,21 +174,21 @@ impl Day for Day8 {
             }
             if merge_idx.len() == 0 {
                 let mut cls = AHashSet::new();
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
                 clusters.push(cls);
             }
             else if merge_idx.len() == 1 {
                 let cls = &mut clusters[merge_idx[0]];
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
             } else {
                 let mut cls = AHashSet::new();
                 for &idx in &merge_idx {
                     cls.extend(clusters[idx].iter().cloned());
                 }
-                cls.insert(self.data[node.pair.0].clone());
-                cls.insert(self.data[node.pair.1].clone());
+                cls.insert(self.data[node.pair.0]);
+                cls.insert(self.data[node.pair.1]);
                 let mut new_cluster = vec![];
                 new_cluster.push(cls);
                 for (i, c) in clusters.into_iter().enumerate() {
@@ -230,10 +204,10 @@ impl Day for Day8 {
             if clusters.len() == 1 {
                 let p1 = &self.data[node.pair.0];
                 let p2 = &self.data[node.pair.1];
-                return (p1.x * p2.x ) as u64;
+                return ((p1.x * p2.x) as u64).into();
             }
             
         }
-        0
+        0u64.into()
     }
 }
@@ -0,0 +1,15 @@
+use aoc::memory::{self, CountingAllocator};
+
+// Installed in a test binary of its own, so the library's unit tests keep the system allocator.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_track() {
+    assert!(memory::counting());
+    let (len, step) = memory::track(|| vec![1u8; 1 << 20].len());
+    assert_eq!(len, 1 << 20);
+    let heap = step.heap.unwrap();
+    assert!(heap.peak >= 1 << 20);
+    assert!(heap.allocations >= 1);
+}